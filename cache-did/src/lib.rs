@@ -9,6 +9,7 @@ pub use pallet::*;
 // mod tests;
 
 mod impls;
+pub mod migrations;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -16,7 +17,7 @@ mod benchmarking;
 #[frame_support::pallet]
 pub mod pallet {
     use cumulus_pallet_xcm::{ensure_relay, Origin as CumulusOrigin};
-    use frame_support::pallet_prelude::*;
+    use frame_support::{pallet_prelude::*, traits::GetStorageVersion};
     use frame_system::pallet_prelude::*;
     use frame_system::Config as SystemConfig;
     use metamui_primitives::{Did, types::DidType};
@@ -48,8 +49,12 @@ pub mod pallet {
         type RelayChainBlockNumber: BlockNumberProvider<BlockNumber = Self::BlockNumber>;
     }
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // the map for storing did information
@@ -127,10 +132,20 @@ pub mod pallet {
         DIDDoesNotExists,
         /// PublicKey already linked to another DID on chain
         PublicKeyRegistered,
+        /// The public key does not map to an account
+        InvalidPublicKey,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if Pallet::<T>::on_chain_storage_version() < 1 {
+                crate::migrations::v1::migrate::<T>()
+            } else {
+                T::DbWeight::get().reads(1)
+            }
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -211,13 +226,9 @@ pub mod pallet {
             PublicKeyMap::<T>::get(did)
         }
 
-        /// Simple type conversion between sr25519::Public and AccountId
-        /// Should not panic for any valid sr25519 - need to make more robust to check for valid
-        /// publicKey
-        pub fn get_accountid_from_pubkey(pk: &PublicKey) -> T::AccountId {
-            //convert a publickey to an accountId
-            // TODO : Need a better way to handle the option failing?
-            T::AccountId::decode(&mut &pk[..]).unwrap()
+        /// Simple type conversion between PublicKey and AccountId
+        pub fn get_accountid_from_pubkey(pk: &PublicKey) -> Result<T::AccountId, DispatchError> {
+            pk.to_account_id::<T::AccountId>().ok_or_else(|| Error::<T>::InvalidPublicKey.into())
         }
 
         /// Check if did is already cached
@@ -230,14 +241,15 @@ pub mod pallet {
 
         /// Create Private Did
         pub fn do_add_did(public_key: &PublicKey, did: &Did, did_type: &DidType) -> DispatchResult {
+            let account_id = Self::get_accountid_from_pubkey(public_key)?;
 
             if Self::is_already_cached(did) {
                 Self::do_remove_did(did)?;
             };
 
             PublicKeyMap::<T>::insert(did.clone(), public_key);
-            Lookup::<T>::insert(did.clone(), Self::get_accountid_from_pubkey(public_key));
-            RLookup::<T>::insert(Self::get_accountid_from_pubkey(public_key), did.clone());
+            Lookup::<T>::insert(did.clone(), &account_id);
+            RLookup::<T>::insert(account_id, did.clone());
             DidTypeMap::<T>::insert(did.clone(), did_type);
 
             let relay_block_number = T::RelayChainBlockNumber::current_block_number();
@@ -257,7 +269,7 @@ pub mod pallet {
             let public_key = PublicKeyMap::<T>::take(did).unwrap();
 
             Lookup::<T>::remove(did.clone());
-            RLookup::<T>::remove(Self::get_accountid_from_pubkey(&public_key));
+            RLookup::<T>::remove(Self::get_accountid_from_pubkey(&public_key)?);
             DidTypeMap::<T>::remove(did.clone());
             LastUpdatedMap::<T>::remove(did.clone());
 
@@ -273,19 +285,20 @@ pub mod pallet {
             );
 
             // ensure the public key is not already linked to a DID
+            let account_id = Self::get_accountid_from_pubkey(public_key)?;
             ensure!(
-                !RLookup::<T>::contains_key(Self::get_accountid_from_pubkey(public_key)),
+                !RLookup::<T>::contains_key(&account_id),
                 Error::<T>::PublicKeyRegistered,
             );
 
             // Remove existing public key
             let old_public_key = PublicKeyMap::<T>::take(did).unwrap();
             Lookup::<T>::remove(did.clone());
-            RLookup::<T>::remove(Self::get_accountid_from_pubkey(&old_public_key));
+            RLookup::<T>::remove(Self::get_accountid_from_pubkey(&old_public_key)?);
 
             // Update new public key
-            Lookup::<T>::insert(did.clone(), Self::get_accountid_from_pubkey(public_key));
-            RLookup::<T>::insert(Self::get_accountid_from_pubkey(public_key), did.clone());
+            Lookup::<T>::insert(did.clone(), &account_id);
+            RLookup::<T>::insert(account_id, did.clone());
             PublicKeyMap::<T>::insert(did.clone(), public_key);
             
             let relay_block_number = T::RelayChainBlockNumber::current_block_number();
@@ -297,14 +310,10 @@ pub mod pallet {
         /// Initialize did during genesis
         fn initialize_dids(dids: &Vec<DidStruct>) {
             for did in dids.iter() {
-                Lookup::<T>::insert(
-                    did.identifier.clone(),
-                    Self::get_accountid_from_pubkey(&did.public_key),
-                );
-                RLookup::<T>::insert(
-                    Self::get_accountid_from_pubkey(&did.public_key),
-                    did.identifier.clone(),
-                );
+                let account_id = Self::get_accountid_from_pubkey(&did.public_key)
+                    .expect("public keys of the genesis dids map to accounts");
+                Lookup::<T>::insert(did.identifier.clone(), &account_id);
+                RLookup::<T>::insert(account_id, did.identifier.clone());
                 PublicKeyMap::<T>::insert(did.identifier.clone(), &did.public_key);
 
                 let did_type = if (&did).is_public == true {
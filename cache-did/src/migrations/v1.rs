@@ -0,0 +1,20 @@
+use crate::pallet::*;
+use frame_support::{
+    traits::{Get, GetStorageVersion},
+    weights::Weight,
+};
+use metamui_primitives::types::PublicKey;
+use sp_core::sr25519;
+
+/// Wrap the sr25519 public key of every cached DID into a multi-scheme public key
+pub fn migrate<T: Config>() -> Weight {
+    let mut translated: u64 = 0;
+    PublicKeyMap::<T>::translate::<sr25519::Public, _>(|_, old| {
+        translated += 1;
+        Some(PublicKey::Sr25519(old))
+    });
+
+    Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
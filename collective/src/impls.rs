@@ -8,4 +8,13 @@ impl<T: Config<I>, I: 'static> IsMember for Pallet<T, I> {
   	// to update those if this is changed.
   	Self::is_member(who)
   }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn add_collective_member(who: &Did) {
+  	Members::<T, I>::mutate(|members| {
+  		if let Err(pos) = members.binary_search(who) {
+  			members.insert(pos, *who);
+  		}
+  	});
+  }
 }
\ No newline at end of file
@@ -22,7 +22,7 @@ use frame_support::{
 	traits::{ConstU32, ConstU64, GenesisBuild},
 	weights::Pays,
 };
-use pallet_did::types::{DIdentity, PrivateDid};
+use pallet_did::types::{DIdentity, PrivateDid, PublicKey};
 use frame_system::{EventRecord, Phase, EnsureSigned};
 use sp_core::{sr25519, Pair, H256};
 use sp_runtime::{
@@ -166,7 +166,7 @@ impl Config for Test {
 
 pub const VALIDATOR_ACCOUNT: u64 = 0;
 pub const VALIDATOR_DID: [u8; 32] = *b"did:ssid:Alice\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-pub const VALIDATOR_PUBKEY: sr25519::Public = sr25519::Public([0; 32]);
+pub const VALIDATOR_PUBKEY: PublicKey = PublicKey::Sr25519(sr25519::Public([0; 32]));
 const ALICE: metamui_primitives::Did = *b"did:ssid:swn\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 const BOB: metamui_primitives::Did = *b"did:ssid:bob\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 const DAVE: metamui_primitives::Did = *b"did:ssid:dave\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
@@ -205,17 +205,17 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		initial_dids: vec![
 			DIdentity::Private(PrivateDid {
 				identifier: BOB,
-				public_key: sr25519::Pair::from_seed(&BOB_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&BOB_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 			DIdentity::Private(PrivateDid {
 				identifier: DAVE,
-				public_key: sr25519::Pair::from_seed(&DAVE_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&DAVE_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 			DIdentity::Private(PrivateDid {
 				identifier: ALICE,
-				public_key: sr25519::Pair::from_seed(&ALICE_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&ALICE_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 			DIdentity::Private(PrivateDid {
@@ -35,6 +35,11 @@ impl<T: Config> DidResolve<T::AccountId> for Pallet<T> {
   fn is_did_public(did: &Did) -> bool {
     Self::check_did_public(did)
   }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn create_private_did(did: Did, public_key: PublicKey) {
+    let _ = Self::do_create_private_did(public_key, did);
+  }
 }
 
 /// implement the lookup trait to fetch the accountid of the
@@ -13,14 +13,14 @@ pub use serde;
 mod benchmarking;
 
 pub mod types;
+pub mod migrations;
 
 mod impls;
 pub use crate::impls::*;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use codec::Decode;
-	use frame_support::{ pallet_prelude::{ *, DispatchResult }, BoundedVec };
+	use frame_support::{ pallet_prelude::{ *, DispatchResult }, traits::GetStorageVersion, BoundedVec };
 	use frame_system::{ self, pallet_prelude::*};
 	use sp_std::vec::Vec;
 	use crate::types::*;
@@ -43,10 +43,25 @@ pub mod pallet {
 		type VCResolution: VCResolve<Self::Hash>;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() < 1 {
+				crate::migrations::v1::migrate::<T>()
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+	}
+
 	// the map for storing did information
 	#[pallet::storage]
 	pub type DIDs<T: Config> =
@@ -124,7 +139,9 @@ pub mod pallet {
     	/// The given VCId does not exist on chain
 		VCIdDoesNotExist,
 		/// The entered VCId is not eligible to create Did
-		InvalidVC
+		InvalidVC,
+		/// The public key does not map to an account
+		InvalidPublicKey,
 	}
 
 	#[pallet::call]
@@ -154,7 +171,7 @@ pub mod pallet {
 			ensure!(Self::verify_did_vc(vc_details.clone(), VCType::PrivateDidVC), Error::<T>::InvalidVC);
 
 			// Decode the VC for getting the metadata and public key
-			let vc_property = T::VCResolution::decode_vc::<PrivateDidVC>(&vc_details.vc_property)?;
+			let vc_property = T::VCResolution::decode_did_vc::<PrivateDidVC>(&vc_id)?;
 
 			// Validate did
 			Self::can_add_did(vc_property.public_key, vc_property.did)?;
@@ -165,8 +182,8 @@ pub mod pallet {
 				vc_property.did,
 			)?;
 
-			// Set the vc to used
-			T::VCResolution::set_is_vc_used(&vc_id, true);
+			// Consume one use of the vc
+			T::VCResolution::consume_vc(&vc_id)?;
 
 			// Emit an event.
 			Self::deposit_event(Event::DidCreated { did: vc_property.did });
@@ -206,7 +223,7 @@ pub mod pallet {
 			ensure!(Self::verify_did_vc(vc_details.clone(), VCType::PublicDidVC), Error::<T>::InvalidVC);
 
 			// Decode the VC for getting the registration number and company name
-			let vc_property = T::VCResolution::decode_vc::<PublicDidVC>(&vc_details.vc_property)?;
+			let vc_property = T::VCResolution::decode_did_vc::<PublicDidVC>(&vc_id)?;
 
 			// Validate did
 			Self::can_add_did(vc_property.public_key, vc_property.did)?;
@@ -219,8 +236,8 @@ pub mod pallet {
 				vc_property.company_name.clone()
 			)?;
 
-			// Set the vc to used
-			T::VCResolution::set_is_vc_used(&vc_id, true);
+			// Consume one use of the vc
+			T::VCResolution::consume_vc(&vc_id)?;
 
 			// Emit an event.
 			Self::deposit_event(Event::DidCreated { did: vc_property.did });
@@ -343,7 +360,7 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Function to validate vc when creating dids
 		pub fn verify_did_vc(vcs_details: VC<T::Hash>, did_vc_type: VCType) -> bool {
-			vcs_details.vc_type == did_vc_type && vcs_details.is_vc_active && !vcs_details.is_vc_used
+			vcs_details.vc_type == did_vc_type && vcs_details.is_vc_active && !vcs_details.is_vc_used()
 		}
 
 		/// Function to check if did which is going to be created is valid or not
@@ -382,13 +399,9 @@ pub mod pallet {
 			}
 		}
 
-		/// Simple type conversion between sr25519::Public and AccountId
-		/// Should not panic for any valid sr25519 - need to make more robust to check for valid
-		/// publicKey
-		pub fn get_accountid_from_pubkey(pk: &PublicKey) -> T::AccountId {
-			//convert a publickey to an accountId
-			// TODO : Need a better way to handle the option failing?
-			T::AccountId::decode(&mut &pk[..]).unwrap()
+		/// Simple type conversion between PublicKey and AccountId
+		pub fn get_accountid_from_pubkey(pk: &PublicKey) -> Result<T::AccountId, DispatchError> {
+			pk.to_account_id::<T::AccountId>().ok_or_else(|| Error::<T>::InvalidPublicKey.into())
 		}
 
 		/// Initialize did during genesis
@@ -436,14 +449,10 @@ pub mod pallet {
 						(public_did.identifier, public_did.public_key)
 					},
 				};
-				Lookup::<T>::insert(
-					identifier.clone(),
-					Self::get_accountid_from_pubkey(&public_key),
-				);
-				RLookup::<T>::insert(
-					Self::get_accountid_from_pubkey(&public_key),
-					identifier,
-				);
+				let account_id = Self::get_accountid_from_pubkey(&public_key)
+					.expect("public keys of the genesis dids map to accounts");
+				Lookup::<T>::insert(identifier.clone(), &account_id);
+				RLookup::<T>::insert(account_id, identifier);
 			}
 		}
 
@@ -460,7 +469,7 @@ pub mod pallet {
 
 			// ensure the public key is not already linked to a DID
 			ensure!(
-				!RLookup::<T>::contains_key(Self::get_accountid_from_pubkey(&public_key)),
+				!RLookup::<T>::contains_key(Self::get_accountid_from_pubkey(&public_key)?),
 				Error::<T>::PublicKeyRegistered
 			);
 
@@ -473,6 +482,7 @@ pub mod pallet {
 			identifier: Did,
 		) -> DispatchResult {
 
+			let account_id = Self::get_accountid_from_pubkey(&public_key)?;
 			let current_block_no = <frame_system::Pallet<T>>::block_number();
 
 			// add DID to the storage
@@ -488,8 +498,6 @@ pub mod pallet {
 				),
 			);
 
-			let account_id = Self::get_accountid_from_pubkey(&public_key);
-
 			Lookup::<T>::insert(identifier.clone(), &account_id);
 			RLookup::<T>::insert(&account_id, identifier.clone());
 
@@ -513,6 +521,7 @@ pub mod pallet {
 			company_name: CompanyName,
 		) -> DispatchResult {
 
+			let account_id = Self::get_accountid_from_pubkey(&public_key)?;
 			let current_block_no = <frame_system::Pallet<T>>::block_number();
 
 			// add DID to the storage
@@ -530,8 +539,6 @@ pub mod pallet {
 				),
 			);
 
-			let account_id = Self::get_accountid_from_pubkey(&public_key);
-
 			Lookup::<T>::insert(identifier.clone(), &account_id);
 			RLookup::<T>::insert(&account_id, identifier.clone());
 
@@ -588,10 +595,8 @@ pub mod pallet {
 			ensure!(DIDs::<T>::contains_key(&identifier), Error::<T>::DIDDoesNotExist);
 
 			// ensure the public key is not already linked to a DID
-			ensure!(
-				!RLookup::<T>::contains_key(Self::get_accountid_from_pubkey(&public_key)),
-				Error::<T>::PublicKeyRegistered
-			);
+			let account_id = Self::get_accountid_from_pubkey(public_key)?;
+			ensure!(!RLookup::<T>::contains_key(&account_id), Error::<T>::PublicKeyRegistered);
 
 			// fetch the existing DID document
 			let (did_doc, last_updated_block) = Self::get_did_details(identifier.clone())?;
@@ -633,30 +638,18 @@ pub mod pallet {
 			};
 
 			// Remove previous lookup of pubkey to DID
-			RLookup::<T>::remove(Self::get_accountid_from_pubkey(
-				&prev_public_key,
-			));
+			let prev_account_id = Self::get_accountid_from_pubkey(&prev_public_key)?;
+			RLookup::<T>::remove(&prev_account_id);
 
 			// Store the previous key to history
 			let mut prev_keys = Self::get_prev_key_details(identifier.clone())?;
-			prev_keys
-				.try_push((
-					Self::get_accountid_from_pubkey(&prev_public_key),
-					last_updated_block,
-				))
-				.ok();
+			prev_keys.try_push((prev_account_id, last_updated_block)).ok();
 
 			PrevKeys::<T>::insert(identifier.clone(), prev_keys);
 
-			Lookup::<T>::insert(
-				identifier.clone(),
-				Self::get_accountid_from_pubkey(&public_key),
-			);
+			Lookup::<T>::insert(identifier.clone(), &account_id);
 
-			RLookup::<T>::insert(
-				Self::get_accountid_from_pubkey(&public_key),
-				identifier.clone(),
-			);
+			RLookup::<T>::insert(account_id, identifier.clone());
 
 			Ok(())
 		}
@@ -665,23 +658,17 @@ pub mod pallet {
 		pub fn do_remove(identifier: &Did) -> DispatchResult {
 			
 			let (did_doc, _) = Self::get_did_details(identifier.clone())?;
+			let public_key = match did_doc {
+				DIdentity::Public(public_did) => public_did.public_key,
+				DIdentity::Private(private_did) => private_did.public_key,
+			};
+			let account_id = Self::get_accountid_from_pubkey(&public_key)?;
 
 			// remove DID from storage
 			DIDs::<T>::remove(&identifier);
 
 			Lookup::<T>::remove(identifier.clone());
-			match did_doc {
-				DIdentity::Public(public_did) => {
-					RLookup::<T>::remove(Self::get_accountid_from_pubkey(
-						&public_did.public_key,
-					));
-				},
-				DIdentity::Private(private_did) => {
-					RLookup::<T>::remove(Self::get_accountid_from_pubkey(
-						&private_did.public_key,
-					));
-				},
-			}
+			RLookup::<T>::remove(account_id);
 
 			Ok(())
 		}
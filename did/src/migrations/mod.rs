@@ -0,0 +1,2 @@
+/// Migration from sr25519 only public keys to multi-scheme public keys
+pub mod v1;
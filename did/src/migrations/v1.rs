@@ -0,0 +1,65 @@
+use crate::{pallet::*, types::*};
+use codec::Decode;
+use frame_support::{
+	traits::{Get, GetStorageVersion},
+	weights::Weight,
+};
+use sp_core::sr25519;
+
+/// Private DID layout before multi-scheme public keys were introduced
+#[derive(Decode)]
+struct OldPrivateDid {
+	identifier: Did,
+	public_key: sr25519::Public,
+	metadata: Metadata,
+}
+
+/// Public DID layout before multi-scheme public keys were introduced
+#[derive(Decode)]
+struct OldPublicDid {
+	identifier: Did,
+	public_key: sr25519::Public,
+	metadata: Metadata,
+	registration_number: RegistrationNumber,
+	company_name: CompanyName,
+}
+
+#[derive(Decode)]
+enum OldDIdentity {
+	Public(OldPublicDid),
+	Private(OldPrivateDid),
+}
+
+impl From<OldDIdentity> for DIdentity {
+	fn from(old: OldDIdentity) -> Self {
+		match old {
+			OldDIdentity::Public(did) => DIdentity::Public(PublicDid {
+				identifier: did.identifier,
+				public_key: PublicKey::Sr25519(did.public_key),
+				metadata: did.metadata,
+				registration_number: did.registration_number,
+				company_name: did.company_name,
+			}),
+			OldDIdentity::Private(did) => DIdentity::Private(PrivateDid {
+				identifier: did.identifier,
+				public_key: PublicKey::Sr25519(did.public_key),
+				metadata: did.metadata,
+			}),
+		}
+	}
+}
+
+/// Wrap the sr25519 public key of every stored DID into a multi-scheme public key
+///
+/// The account ids linked to the DIDs do not change, so `Lookup` and `RLookup` are left as is.
+pub fn migrate<T: Config>() -> Weight {
+	let mut translated: u64 = 0;
+	DIDs::<T>::translate::<(OldDIdentity, T::BlockNumber), _>(|_, (old, block_number)| {
+		translated += 1;
+		Some((old.into(), block_number))
+	});
+
+	Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+}
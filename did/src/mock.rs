@@ -14,6 +14,7 @@ use frame_system as system;
 use sp_runtime::{
 	testing::Header,
 	traits::{ BlakeTwo256, IdentityLookup, Hash },
+	MultiSignature,
 };
 use system::EnsureSigned;
 use sp_std::iter::*;
@@ -42,6 +43,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Did: pallet_did::{Pallet, Call, Storage, Event<T>, Config<T>},
 		VcPallet: pallet_vc::{Pallet, Call, Storage, Event<T>, Config<T>},
 	}
@@ -96,7 +98,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -119,6 +121,32 @@ impl pallet_vc::Config for Test {
 	type IsCouncilMember = ();
 	type IsValidator = IsValidatorImplemented;
 	type DidResolution = Did;
+	type TransferOrigin = system::EnsureRoot<Self::AccountId>;
+	type RetentionPeriod = ConstU64<10>;
+	type MaxPrunedPerBlock = ConstU32<2>;
+	type MaxRetiredPerBlock = ConstU32<3>;
+	type MaxSupersedeDepth = ConstU32<2>;
+	type MaxIssuers = ConstU32<10>;
+	type MaxSignaturesPerBatch = ConstU32<4>;
+	type Currency = Balances;
+	type VCDepositBase = ConstU64<0>;
+	type VCDepositPerByte = ConstU64<0>;
+	type VCLinks = ();
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type DidResolution = Did;
+	type ApproveOrigin = system::EnsureRoot<Self::AccountId>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -132,14 +160,14 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			DIdentity::Private(
 				PrivateDid {
 					identifier: VALIDATOR_DID,
-					public_key: sr25519::Pair::from_seed(&VALIDATOR_SEED).public(),
+					public_key: sr25519::Pair::from_seed(&VALIDATOR_SEED).public().into(),
 					metadata: Default::default(),
 				}
 			),
 			DIdentity::Private(
 				PrivateDid {
 					identifier: REGIONAL_DID,
-					public_key: sr25519::Pair::from_seed(&REGIONAL_SEED).public(),
+					public_key: sr25519::Pair::from_seed(&REGIONAL_SEED).public().into(),
 					metadata: Default::default(),
 				}
 			)
@@ -180,13 +208,14 @@ pub fn get_vc_id_and_hex(did_vc_bytes: [u8; 128], vc_type: VCType) -> ([u8; 32],
 	let owner = VALIDATOR_DID;
 	let issuers = vec![VALIDATOR_DID];
 	let hash = BlakeTwo256::hash_of(&(&vc_type, &did_vc_bytes, &owner, &issuers));
-	let signature = pair.sign(hash.as_ref());
+	let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 	let vc_struct = VC {
 		hash,
 		owner,
 		issuers,
 		signatures: vec![signature],
-		is_vc_used: false,
+		max_uses: Some(1),
+		used_count: 0,
 		is_vc_active: true,
 		vc_type,
 		vc_property: did_vc_bytes,
@@ -11,11 +11,11 @@ use sp_core::{sr25519, Pair, H256};
 #[test]
 fn test_genesis_worked() {
 	new_test_ext().execute_with(|| {
-		let validator_pubkey: sr25519::Public = sr25519::Pair::from_seed(&VALIDATOR_SEED).public();
+		let validator_pubkey: PublicKey = sr25519::Pair::from_seed(&VALIDATOR_SEED).public().into();
 		assert_eq!(DIDs::<Test>::contains_key(VALIDATOR_DID.clone()), true);
 		assert_eq!(Lookup::<Test>::contains_key(VALIDATOR_DID.clone()), true);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&validator_pubkey)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&validator_pubkey).unwrap()),
 			true
 		);
 
@@ -31,11 +31,11 @@ fn test_genesis_worked() {
 			},
 		}
 
-		let regional_pubkey: sr25519::Public = sr25519::Pair::from_seed(&REGIONAL_SEED).public();
+		let regional_pubkey: PublicKey = sr25519::Pair::from_seed(&REGIONAL_SEED).public().into();
 		assert_eq!(DIDs::<Test>::contains_key(REGIONAL_DID.clone()), true);
 		assert_eq!(Lookup::<Test>::contains_key(REGIONAL_DID.clone()), true);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&regional_pubkey)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&regional_pubkey).unwrap()),
 			true
 		);
 
@@ -62,7 +62,7 @@ fn test_genesis_worked() {
 fn test_local_validator_adds_private_did_in_their_region() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:region:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -82,7 +82,7 @@ fn test_local_validator_adds_private_did_in_their_region() {
 fn test_local_validator_adds_public_did_in_their_region() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:region:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -102,7 +102,7 @@ fn test_local_validator_adds_public_did_in_their_region() {
 fn test_local_validator_adds_private_did_in_another_region() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:region2:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (_, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -117,7 +117,7 @@ fn test_local_validator_adds_private_did_in_another_region() {
 fn test_local_validator_adds_public_did_in_another_region() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:region2:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (_, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -132,7 +132,7 @@ fn test_local_validator_adds_public_did_in_another_region() {
 fn test_global_validator_adds_regional_private_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:region:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -152,7 +152,7 @@ fn test_global_validator_adds_regional_private_did() {
 fn test_global_validator_adds_regional_public_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:region:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -172,7 +172,7 @@ fn test_global_validator_adds_regional_public_did() {
 fn test_add_invalid_private_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"d\0d:ssid:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -192,7 +192,7 @@ fn test_add_invalid_private_did() {
 fn test_add_invalid_public_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"d\0d:ssid:Alice2\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -212,7 +212,7 @@ fn test_add_invalid_public_did() {
 fn test_add_private_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:ssid:Bob\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -227,7 +227,7 @@ fn test_add_private_did() {
 		assert_eq!(DIDs::<Test>::contains_key(identifier.clone()), true);
 		assert_eq!(Lookup::<Test>::contains_key(identifier.clone()), true);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			true
 		);
 
@@ -236,7 +236,7 @@ fn test_add_private_did() {
 			types::DIdentity::Private(private_did) => {
 				assert_eq!(private_did.identifier, identifier);
 				assert_eq!(private_did.public_key, public_key);
-				let did_lookup = RLookup::<Test>::get(Did::get_accountid_from_pubkey(&public_key));
+				let did_lookup = RLookup::<Test>::get(Did::get_accountid_from_pubkey(&public_key).unwrap());
 				match did_lookup {
 					Some(did) => assert_eq!(did, identifier.clone()),
 					None => assert!(false),
@@ -253,7 +253,7 @@ fn test_add_private_did() {
 fn test_add_public_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:ssid:Bob\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 		
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -269,7 +269,7 @@ fn test_add_public_did() {
 		assert_eq!(DIDs::<Test>::contains_key(identifier.clone()), true);
 		assert_eq!(Lookup::<Test>::contains_key(identifier.clone()), true);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			true
 		);
 
@@ -278,7 +278,7 @@ fn test_add_public_did() {
 			types::DIdentity::Public(public_did) => {
 				assert_eq!(public_did.identifier, identifier);
 				assert_eq!(public_did.public_key, public_key);
-				let did_lookup = RLookup::<Test>::get(Did::get_accountid_from_pubkey(&public_key));
+				let did_lookup = RLookup::<Test>::get(Did::get_accountid_from_pubkey(&public_key).unwrap());
 				match did_lookup {
 					Some(did) => assert_eq!(did, identifier.clone()),
 					None => assert!(false),
@@ -296,7 +296,7 @@ fn test_add_existing_private_did() {
 	new_test_ext().execute_with(|| {
 		// Adding the DID initialized at the time of genesis, so this test should fail
 		let identifier = *b"did:ssid:swn\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -317,7 +317,7 @@ fn test_add_existing_public_did() {
 	new_test_ext().execute_with(|| {
 		// Adding the DID initialized at the time of genesis, so this test should fail
 		let identifier = VALIDATOR_DID;
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -339,7 +339,7 @@ fn test_add_existing_pubkey_private_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:ssid:Alicx\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 		// To generate the same public key as the one used in genesis so it will throw error
-		let public_key = sr25519::Pair::from_seed(&VALIDATOR_SEED).public();
+		let public_key: PublicKey = sr25519::Pair::from_seed(&VALIDATOR_SEED).public().into();
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (_, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -354,7 +354,7 @@ fn test_add_existing_pubkey_private_did() {
 fn test_add_existing_pubkey_public_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:ssid:Alicx\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Pair::from_seed(&VALIDATOR_SEED).public();
+		let public_key: PublicKey = sr25519::Pair::from_seed(&VALIDATOR_SEED).public().into();
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (_, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -390,7 +390,7 @@ fn test_non_validator_removes_did() {
 fn test_remove_private_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:ssid:Alicx\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_private_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PrivateDidVC);
@@ -408,7 +408,7 @@ fn test_remove_private_did() {
 		assert_eq!(DIDs::<Test>::contains_key(identifier.clone()), false);
 		assert_eq!(Lookup::<Test>::contains_key(identifier.clone()), false);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			false
 		);
 	})
@@ -420,7 +420,7 @@ fn test_remove_private_did() {
 fn test_remove_public_did() {
 	new_test_ext().execute_with(|| {
 		let identifier = *b"did:ssid:Alicx\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-		let public_key = sr25519::Public(identifier);
+		let public_key = PublicKey::Sr25519(sr25519::Public(identifier));
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
 		let (did_vc_id, did_vc_hex) = get_vc_id_and_hex(did_vc_bytes, VCType::PublicDidVC);
@@ -438,7 +438,7 @@ fn test_remove_public_did() {
 		assert_eq!(DIDs::<Test>::contains_key(identifier.clone()), false);
 		assert_eq!(Lookup::<Test>::contains_key(identifier.clone()), false);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			false
 		);
 	})
@@ -480,11 +480,11 @@ fn test_rotate_key_for_private_did() {
 
 		// Ensure only a singly pubkey is mapped to a DID - inspired from toufeeq's testing
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			false
 		);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2).unwrap()),
 			true
 		);
 
@@ -505,7 +505,7 @@ fn test_rotate_key_for_private_did() {
 		assert_eq!(prev_key_list.len(), 1);
 
 		let (last_pub_key, block_number) = prev_key_list.first().cloned().unwrap();
-		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key));
+		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key).unwrap());
 		assert_eq!(block_number, 0);
 	})
 }
@@ -546,11 +546,11 @@ fn test_rotate_key_for_public_did() {
 
 		// Ensure only a singly pubkey is mapped to a DID - inspired from toufeeq's testing
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			false
 		);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2).unwrap()),
 			true
 		);
 
@@ -571,7 +571,7 @@ fn test_rotate_key_for_public_did() {
 		assert_eq!(prev_key_list.len(), 1);
 
 		let (last_pub_key, block_number) = prev_key_list.first().cloned().unwrap();
-		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key));
+		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key).unwrap());
 		assert_eq!(block_number, 0);
 	})
 }
@@ -623,15 +623,15 @@ fn test_rotate_key_history_for_private_did() {
 
 		// Ensure only a singly pubkey is mapped to a DID -  inspired from toufeeq's testing
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			false
 		);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2).unwrap()),
 			false
 		);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key3)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key3).unwrap()),
 			true
 		);
 
@@ -653,11 +653,11 @@ fn test_rotate_key_history_for_private_did() {
 		assert_eq!(prev_key_list.len(), 2);
 
 		let (last_pub_key, block_number) = prev_key_list[0];
-		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key));
+		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key).unwrap());
 		assert_eq!(block_number, 0);
 
 		let (last_pub_key2, block_number2) = prev_key_list[1];
-		assert_eq!(last_pub_key2, Did::get_accountid_from_pubkey(&public_key2));
+		assert_eq!(last_pub_key2, Did::get_accountid_from_pubkey(&public_key2).unwrap());
 		assert_eq!(block_number2, 3);
 	})
 }
@@ -709,15 +709,15 @@ fn test_rotate_key_history_for_public_did() {
 
 		// Ensure only a singly pubkey is mapped to a DID -  inspired from toufeeq's testing
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key).unwrap()),
 			false
 		);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key2).unwrap()),
 			false
 		);
 		assert_eq!(
-			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key3)),
+			RLookup::<Test>::contains_key(Did::get_accountid_from_pubkey(&public_key3).unwrap()),
 			true
 		);
 
@@ -739,11 +739,11 @@ fn test_rotate_key_history_for_public_did() {
 		assert_eq!(prev_key_list.len(), 2);
 
 		let (last_pub_key, block_number) = prev_key_list[0];
-		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key));
+		assert_eq!(last_pub_key, Did::get_accountid_from_pubkey(&public_key).unwrap());
 		assert_eq!(block_number, 0);
 
 		let (last_pub_key2, block_number2) = prev_key_list[1];
-		assert_eq!(last_pub_key2, Did::get_accountid_from_pubkey(&public_key2));
+		assert_eq!(last_pub_key2, Did::get_accountid_from_pubkey(&public_key2).unwrap());
 		assert_eq!(block_number2, 3);
 	})
 }
@@ -1060,7 +1060,7 @@ fn test_check_did_public_with_public_did() {
 #[test]
 fn test_get_account_id_from_public_key_of_private_did() {
 	new_test_ext().execute_with(|| {
-		let account_id = Did::get_accountid_from_pubkey(&sr25519::Pair::from_seed(&VALIDATOR_SEED).public());
+		let account_id = Did::get_accountid_from_pubkey(&sr25519::Pair::from_seed(&VALIDATOR_SEED).public().into()).unwrap();
 		assert_eq!(account_id, VALIDATOR_ACCOUNT);
 	})
 }
@@ -1075,7 +1075,7 @@ fn test_get_account_id_from_public_key_of_public_did() {
 			57, 143, 12, 40, 249, 136, 133, 224, 70, 51, 61, 74, 65, 193, 156, 238, 76, 55, 54, 138, 152,
 			50, 198, 80, 47, 108, 253, 24, 46, 42, 239, 137,
 		];
-		let public_key = sr25519::Pair::from_seed(&bob_seed).public();
+		let public_key: PublicKey = sr25519::Pair::from_seed(&bob_seed).public().into();
 		let bob_account_id: u64 = 7166219960988249998;
 
 		let did_vc_bytes = get_public_did_vc(identifier, public_key);
@@ -1089,7 +1089,7 @@ fn test_get_account_id_from_public_key_of_public_did() {
 			None
 		));
 
-		let account_id = Did::get_accountid_from_pubkey(&public_key);
+		let account_id = Did::get_accountid_from_pubkey(&public_key).unwrap();
 		assert_eq!(account_id, bob_account_id);
 	})
 }
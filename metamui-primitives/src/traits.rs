@@ -18,6 +18,9 @@ pub trait DidResolve<AccountId> {
   fn get_public_key(k: &Did) -> Option<PublicKey>;
 	/// Check if did is public
 	fn is_did_public(did: &Did) -> bool;
+	/// Register a private did, only used to set up benchmarks
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_private_did(_did: Did, _public_key: PublicKey) {}
 }
 
 impl<AccountId> DidResolve<AccountId> for () {
@@ -88,14 +91,26 @@ impl<AccountId: Default> Default for MultiAddress<AccountId> {
 // VC
 /// Trait to get VC details
 pub trait VCResolve<Hash> {
-	/// Get VC from VC Id
+	/// Get VC from VC Id, a replaced VC resolves to its latest version
 	fn get_vc(vc_id: &VCid) -> Option<VC<Hash>>;
+	/// Get the id of the latest version of VC, the id itself if VC was not replaced
+	fn latest_vc_id(vc_id: &VCid) -> VCid;
+	/// Get the Did currently owning the VC, which differs from the VC owner after a transfer
+	fn get_vc_owner(vc_id: &VCid) -> Option<Did>;
 	/// Get if VC is used
 	fn is_vc_used(vc_id: &VCid) -> bool;
-	/// Set VC used
-	fn set_is_vc_used(vc_id: &VCid, is_vc_used: bool);
+	/// Consume one use of VC, returns the remaining uses (`None` if unlimited)
+	fn consume_vc(vc_id: &VCid) -> Result<Option<u32>, DispatchError>;
+	/// Consume part of the amount carried by VC, returns the amount left
+	fn consume_vc_amount(vc_id: &VCid, amount: u128) -> Result<u128, DispatchError>;
 	/// Decode VC
 	fn decode_vc<E: Decode>(vc_bytes: &[u8]) -> Result<E, DispatchError>;
+	/// Decode the property of a DID VC, resolved like `get_vc`
+	///
+	/// DID VCs stored before multi-scheme keys were introduced hold the legacy property layout.
+	fn decode_did_vc<E: DidVCProperty>(vc_id: &VCid) -> Result<E, DispatchError>;
+	/// Get all the stored VCs of the type, it reads the whole store and is meant for migrations
+	fn vcs_of_type(vc_type: VCType) -> Vec<(VCid, VC<Hash>)>;
 }
 
 impl<Hash> VCResolve<Hash> for () {
@@ -103,18 +118,52 @@ impl<Hash> VCResolve<Hash> for () {
 	fn get_vc(_vc_id: &VCid) -> Option<VC<Hash>> {
 		None
 	}
+	/// Get the id of the latest version of VC
+	fn latest_vc_id(vc_id: &VCid) -> VCid {
+		*vc_id
+	}
+	/// Get the Did currently owning the VC
+	fn get_vc_owner(_vc_id: &VCid) -> Option<Did> {
+		None
+	}
 	/// Get if VC is used
 	fn is_vc_used(_vc_id: &VCid) -> bool {
 		true
 	}
-	/// Set VC used
-	fn set_is_vc_used(_vc_id: &VCid, _is_vc_used: bool) {
-		()
+	/// Consume one use of VC
+	fn consume_vc(_vc_id: &VCid) -> Result<Option<u32>, DispatchError> {
+		Err("Not Implemented".into())
+	}
+	/// Consume part of the amount carried by VC
+	fn consume_vc_amount(_vc_id: &VCid, _amount: u128) -> Result<u128, DispatchError> {
+		Err("Not Implemented".into())
 	}
 	/// Decode VC
 	fn decode_vc<E: Decode>(_vc_bytes: &[u8]) -> Result<E, DispatchError> {
 		Err("Not Implemented".into())
 	}
+	/// Decode the property of a DID VC
+	fn decode_did_vc<E: DidVCProperty>(_vc_id: &VCid) -> Result<E, DispatchError> {
+		Err("Not Implemented".into())
+	}
+	/// Get all the stored VCs of the type
+	fn vcs_of_type(_vc_type: VCType) -> Vec<(VCid, VC<Hash>)> {
+		Vec::new()
+	}
+}
+
+/// Property of a DID VC, along with its layout before multi-scheme keys
+pub trait DidVCProperty: Decode {
+	/// Layout of the property with an sr25519 key
+	type Legacy: Decode + Into<Self>;
+}
+
+impl DidVCProperty for PublicDidVC {
+	type Legacy = LegacyPublicDidVC;
+}
+
+impl DidVCProperty for PrivateDidVC {
+	type Legacy = LegacyPrivateDidVC;
 }
 
 /// Trait to give back the VCid
@@ -139,10 +188,40 @@ impl HasVCId for TokenTransferVC {
 	}
 }
 
+/// Trait for pallets that depend on stored VCs
+pub trait VCLinks<Hash> {
+	/// Whether another pallet still depends on the VC, a linked VC is not pruned
+	fn is_vc_linked(vc_id: &VCid) -> bool;
+	/// Check that the VC can be replaced by the new version
+	fn can_supersede(vc_id: &VCid, old_vc: &VC<Hash>, vc: &VC<Hash>) -> Result<(), DispatchError>;
+	/// The new version `by` of the VC is active and replaces it
+	fn on_superseded(
+		vc_id: &VCid,
+		old_vc: &VC<Hash>,
+		by: &VCid,
+		vc: &VC<Hash>,
+	) -> Result<(), DispatchError>;
+}
+
+impl<Hash> VCLinks<Hash> for () {
+	fn is_vc_linked(_: &VCid) -> bool {
+		false
+	}
+	fn can_supersede(_: &VCid, _: &VC<Hash>, _: &VC<Hash>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+	fn on_superseded(_: &VCid, _: &VC<Hash>, _: &VCid, _: &VC<Hash>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+}
+
 /// Trait to check if a Did is a council member
 pub trait IsMember {
 	/// Function to check council membership
 	fn is_collective_member(_: &Did) -> bool;
+	/// Add a council member, only used to set up benchmarks
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_collective_member(_: &Did) {}
 }
 
 impl IsMember for () {
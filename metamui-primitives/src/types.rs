@@ -1,17 +1,88 @@
 use super::*;
-use codec::{Decode, Encode};
+use codec::{Decode, DecodeAll, Encode};
 use sp_runtime::RuntimeDebug;
 use scale_info::TypeInfo;
 use frame_support::{traits::ConstU32, BoundedVec};
 use frame_support::pallet_prelude::MaxEncodedLen;
-use sp_core::sr25519::Signature as SRSignature;
-use sp_core::sr25519;
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_runtime::{AccountId32, MultiSigner};
+
+/// Serde support for arrays longer than 32 elements
+#[cfg(feature = "serde")]
+mod serde_array {
+  use serde_big_array::big_array;
+  big_array! { BigArray; }
+}
 
 /// VC Property type
 pub type VCProperty = [u8; 128];
 
-/// Public Key Type
-pub type PublicKey = sr25519::Public;
+/// Public Key Type, a DID can hold a key of any supported signature scheme
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PublicKey {
+  /// Sr25519 public key
+  Sr25519(sr25519::Public),
+  /// Ed25519 public key
+  Ed25519(ed25519::Public),
+  /// Compressed ECDSA (secp256k1) public key
+  Ecdsa(ecdsa::Public),
+}
+
+impl PublicKey {
+  /// Verify signature on message, the signature has to use the same scheme as the key
+  pub fn verify_signature(&self, signature: &MultiSignature, msg: &[u8]) -> bool {
+    match (self, signature) {
+      (PublicKey::Sr25519(public), MultiSignature::Sr25519(signature)) => signature.verify(msg, public),
+      (PublicKey::Ed25519(public), MultiSignature::Ed25519(signature)) => signature.verify(msg, public),
+      (PublicKey::Ecdsa(public), MultiSignature::Ecdsa(signature)) => signature.verify(msg, public),
+      _ => false,
+    }
+  }
+
+  /// Convert the public key to the account id linked to it
+  pub fn to_account_id<AccountId: Decode>(&self) -> Option<AccountId> {
+    let account = (*self).into_account();
+    AccountId::decode(&mut AsRef::<[u8]>::as_ref(&account)).ok()
+  }
+}
+
+impl IdentifyAccount for PublicKey {
+  type AccountId = AccountId32;
+
+  fn into_account(self) -> AccountId32 {
+    MultiSigner::from(self).into_account()
+  }
+}
+
+impl From<PublicKey> for MultiSigner {
+  fn from(public_key: PublicKey) -> Self {
+    match public_key {
+      PublicKey::Sr25519(public) => MultiSigner::Sr25519(public),
+      PublicKey::Ed25519(public) => MultiSigner::Ed25519(public),
+      PublicKey::Ecdsa(public) => MultiSigner::Ecdsa(public),
+    }
+  }
+}
+
+impl From<sr25519::Public> for PublicKey {
+  fn from(public: sr25519::Public) -> Self {
+    PublicKey::Sr25519(public)
+  }
+}
+
+impl From<ed25519::Public> for PublicKey {
+  fn from(public: ed25519::Public) -> Self {
+    PublicKey::Ed25519(public)
+  }
+}
+
+impl From<ecdsa::Public> for PublicKey {
+  fn from(public: ecdsa::Public) -> Self {
+    PublicKey::Ecdsa(public)
+  }
+}
+
 /// Maximum Size of Metadata
 pub type MaxMetadata = ConstU32<32>;
 /// Maximum Length of Registration Number
@@ -51,6 +122,7 @@ pub enum VCType {
 
 /// Struct for VC
 #[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VC<Hash> {
   /// Hash of the data in VC
   pub hash: Hash,
@@ -59,17 +131,117 @@ pub struct VC<Hash> {
   /// Issuers of VC
   pub issuers: Vec<Did>,
   /// Signatures of Issuers on hash
-  pub signatures: Vec<SRSignature>,
-  /// If VC is used or not
-  pub is_vc_used: bool,
+  pub signatures: Vec<MultiSignature>,
+  /// Maximum number of times the VC can be used, `None` for unlimited uses
+  pub max_uses: Option<u32>,
+  /// Number of times the VC has been used
+  pub used_count: u32,
   /// If VC is active or not
   pub is_vc_active: bool,
   /// Type of VC
   pub vc_type: VCType,
   /// VC payload
+  #[cfg_attr(feature = "serde", serde(with = "serde_array::BigArray"))]
   pub vc_property: VCProperty,
 }
 
+impl<Hash> VC<Hash> {
+  /// Check if VC has no uses left
+  pub fn is_vc_used(&self) -> bool {
+    match self.max_uses {
+      Some(max_uses) => self.used_count >= max_uses,
+      None => false,
+    }
+  }
+
+  /// Remaining uses of VC, `None` if VC can be used any number of times
+  pub fn remaining_uses(&self) -> Option<u32> {
+    self.max_uses.map(|max_uses| max_uses.saturating_sub(self.used_count))
+  }
+}
+
+/// Prefix of an encoded `VersionedVC`, encoded bytes without it are a v1 `VC`
+///
+/// A v1 `VC` starts with its hash, so any prefix can begin a v1 `VC` too. Bytes that are a
+/// complete v1 `VC` are always read as one, see `VersionedVC::decode_envelope`.
+pub const VC_ENVELOPE_MAGIC: [u8; 4] = *b"mvc\x00";
+
+/// Fields added to the VC in version 2, they are covered by the VC hash
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VCExtension {
+  /// Last block in which the VC can be used, `None` if it does not expire
+  pub expires_at: Option<u32>,
+  /// Hash of the schema the VC property follows
+  pub schema: Option<[u8; 32]>,
+}
+
+/// Version 2 of the VC
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VCv2<Hash> {
+  /// The VC, its hash also covers the extension
+  pub vc: VC<Hash>,
+  /// Fields added in version 2
+  pub extension: VCExtension,
+}
+
+/// Envelope of the VC formats, so new versions can be added without breaking the existing ones
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VersionedVC<Hash> {
+  /// The original VC layout
+  #[codec(index = 1)]
+  V1(VC<Hash>),
+  /// VC with an extension
+  #[codec(index = 2)]
+  V2(VCv2<Hash>),
+}
+
+impl<Hash: Decode> VersionedVC<Hash> {
+  /// Decode an envelope, or a bare v1 `VC` for the bytes without the envelope prefix
+  ///
+  /// Bytes that decode entirely as a v1 `VC` are read as one first, so a v1 `VC` whose hash
+  /// happens to start with the envelope prefix is not mistaken for an envelope.
+  pub fn decode_envelope(mut bytes: &[u8]) -> Result<Self, codec::Error> {
+    if let Ok(vc) = VC::decode_all(&mut &bytes[..]) {
+      return Ok(VersionedVC::V1(vc));
+    }
+    if bytes.starts_with(&VC_ENVELOPE_MAGIC) {
+      Self::decode(&mut &bytes[VC_ENVELOPE_MAGIC.len()..])
+    } else {
+      VC::decode(&mut bytes).map(VersionedVC::V1)
+    }
+  }
+}
+
+impl<Hash: Encode> VersionedVC<Hash> {
+  /// Encode the VC in an envelope
+  pub fn encode_envelope(&self) -> Vec<u8> {
+    let mut bytes = VC_ENVELOPE_MAGIC.to_vec();
+    self.encode_to(&mut bytes);
+    bytes
+  }
+}
+
+impl<Hash> VersionedVC<Hash> {
+  /// Version of the VC format
+  pub fn version(&self) -> u8 {
+    match self {
+      VersionedVC::V1(_) => 1,
+      VersionedVC::V2(_) => 2,
+    }
+  }
+
+  /// Split into the VC and its extension, v1 VCs have no extension
+  pub fn into_parts(self) -> (VC<Hash>, Option<VCExtension>) {
+    match self {
+      VersionedVC::V1(vc) => (vc, None),
+      VersionedVC::V2(VCv2 { vc, extension }) => (vc, Some(extension)),
+    }
+  }
+}
+
 /// SlashMintTokens Type VC
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -118,6 +290,45 @@ pub struct PrivateDidVC {
   pub did: Did,
 }
 
+/// PublicDidVC property of the VCs stored before multi-scheme keys, the key is sr25519
+#[derive(Decode)]
+pub struct LegacyPublicDidVC {
+  /// Public Key
+  pub public_key: sr25519::Public,
+  /// Registration Number
+  pub registration_number: RegistrationNumber,
+  /// Name of Company
+  pub company_name: CompanyName,
+  /// Did
+  pub did: Did,
+}
+
+impl From<LegacyPublicDidVC> for PublicDidVC {
+  fn from(legacy: LegacyPublicDidVC) -> Self {
+    PublicDidVC {
+      public_key: legacy.public_key.into(),
+      registration_number: legacy.registration_number,
+      company_name: legacy.company_name,
+      did: legacy.did,
+    }
+  }
+}
+
+/// PrivateDidVC property of the VCs stored before multi-scheme keys, the key is sr25519
+#[derive(Decode)]
+pub struct LegacyPrivateDidVC {
+  /// Public Key
+  pub public_key: sr25519::Public,
+  /// Did
+  pub did: Did,
+}
+
+impl From<LegacyPrivateDidVC> for PrivateDidVC {
+  fn from(legacy: LegacyPrivateDidVC) -> Self {
+    PrivateDidVC { public_key: legacy.public_key.into(), did: legacy.did }
+  }
+}
+
 /// VC used to create Tokens
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -130,6 +341,11 @@ pub struct TokenVC {
   pub decimal: u8,
   /// Currency Code
   pub currency_code: CurrencyCode,
+  /// Maximum supply of the token, `None` if the supply is not capped
+  ///
+  /// The VC property is zero padded, so TokenVCs issued before the cap existed decode as
+  /// uncapped.
+  pub max_supply: Option<u128>,
 }
 
 /// Did Type 
@@ -0,0 +1,204 @@
+//! RPC interface for pallet-token
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use metamui_primitives::{types::CurrencyCode, Did};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use token_runtime_api::{TokenApi as TokenRuntimeApi, TokenInfo};
+
+/// Number of holders returned in a page when no page size is given
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+/// Maximum number of holders returned in a page
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Balance of a token held by a Did
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalance<Balance> {
+	/// Currency code of the token
+	pub currency_code: CurrencyCode,
+	/// Free balance of the token
+	pub balance: Balance,
+	/// Balance of the token held, e.g. for pending redemptions or escrows
+	pub held: Balance,
+}
+
+/// A holder of a token along with its balance
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Holder<Balance> {
+	/// Did of the holder
+	pub did: Did,
+	/// Free balance of the token
+	pub balance: Balance,
+	/// Balance of the token held, e.g. for pending redemptions or escrows
+	pub held: Balance,
+}
+
+/// A page of results
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+	/// Entries of the page
+	pub items: Vec<T>,
+	/// Index of the page, starting at 0
+	pub page: u32,
+	/// Index of the next page, `None` if this is the last page
+	pub next_page: Option<u32>,
+}
+
+#[rpc(client, server)]
+pub trait TokenApi<BlockHash, AccountId, Balance> {
+	/// The token registered with the currency code
+	#[method(name = "token_tokenInfo")]
+	fn token_info(
+		&self,
+		currency_code: CurrencyCode,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<TokenInfo<AccountId, Balance>>>;
+
+	/// Free and held balances of the tokens the Did holds
+	#[method(name = "token_balancesOf")]
+	fn balances_of(
+		&self,
+		did: Did,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<TokenBalance<Balance>>>;
+
+	/// Holders of the token with their free and held balances
+	#[method(name = "token_holders")]
+	fn holders(
+		&self,
+		currency_code: CurrencyCode,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<Holder<Balance>>>;
+}
+
+/// Provides RPC methods to query the tokens registered on chain
+pub struct Tokens<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Tokens<C, P> {
+	/// Create new `Tokens` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested page size is not allowed.
+	InvalidPageSize,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidPageSize => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query tokens.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+/// Offset and page size of the requested page
+fn page_bounds(page: Option<u32>, page_size: Option<u32>) -> RpcResult<(u32, u32)> {
+	let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+	if page_size == 0 || page_size > MAX_PAGE_SIZE {
+		return Err(CallError::Custom(ErrorObject::owned(
+			Error::InvalidPageSize.into(),
+			"Page size must be between 1 and the maximum page size.",
+			Some(MAX_PAGE_SIZE),
+		))
+		.into())
+	}
+	let offset = page.unwrap_or(0).saturating_mul(page_size);
+	Ok((offset, page_size))
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance> TokenApiServer<<Block as BlockT>::Hash, AccountId, Balance>
+	for Tokens<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: TokenRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+	Balance: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+	fn token_info(
+		&self,
+		currency_code: CurrencyCode,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<TokenInfo<AccountId, Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.token_info(&at, currency_code).map_err(runtime_error)
+	}
+
+	fn balances_of(
+		&self,
+		did: Did,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<TokenBalance<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let balances = api.balances_of(&at, did).map_err(runtime_error)?;
+		Ok(balances
+			.into_iter()
+			.map(|(currency_code, balance, held)| TokenBalance { currency_code, balance, held })
+			.collect())
+	}
+
+	fn holders(
+		&self,
+		currency_code: CurrencyCode,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Page<Holder<Balance>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let (offset, page_size) = page_bounds(page, page_size)?;
+
+		// one holder more than the page size is fetched to know if there is a next page
+		let mut holders =
+			api.holders(&at, currency_code, offset, page_size + 1).map_err(runtime_error)?;
+		let page = page.unwrap_or(0);
+		let has_next = holders.len() > page_size as usize;
+		holders.truncate(page_size as usize);
+		Ok(Page {
+			items: holders
+				.into_iter()
+				.map(|(did, balance, held)| Holder { did, balance, held })
+				.collect(),
+			page,
+			next_page: if has_next { page.checked_add(1) } else { None },
+		})
+	}
+}
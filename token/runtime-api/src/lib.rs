@@ -0,0 +1,29 @@
+//! Runtime API definition for pallet-token
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use metamui_primitives::{types::CurrencyCode, Did};
+pub use pallet_token::types::{TokenInfo, VestingBalance};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// API to query the tokens registered on chain and their holders
+	pub trait TokenApi<AccountId, Balance> where AccountId: Codec, Balance: Codec {
+		/// The token registered with the currency code, `None` if there is none
+		fn token_info(currency_code: CurrencyCode) -> Option<TokenInfo<AccountId, Balance>>;
+
+		/// Free and held balances of the tokens the Did holds
+		fn balances_of(did: Did) -> Vec<(CurrencyCode, Balance, Balance)>;
+
+		/// Holders of the token with their free and held balances, at most `limit` of them
+		/// starting at `offset`
+		fn holders(
+			currency_code: CurrencyCode,
+			offset: u32,
+			limit: u32,
+		) -> Vec<(Did, Balance, Balance)>;
+
+		/// Amounts of the token of the Did locked by vesting, and vested but not claimed yet
+		fn vesting_balance(currency_code: CurrencyCode, did: Did) -> VestingBalance<Balance>;
+	}
+}
@@ -0,0 +1,202 @@
+use super::pallet::*;
+use frame_support::{
+	ensure,
+	sp_runtime::{
+		traits::{CheckedAdd, Saturating, Zero},
+		DispatchError, DispatchResult,
+	},
+	traits::{
+		tokens::{fungibles, DepositConsequence, WithdrawConsequence},
+		NamedReservableCurrency,
+	},
+};
+use metamui_primitives::{
+	traits::{DidResolve, VCLinks, VCResolve},
+	types::{CurrencyCode, TokenVC, VC},
+	Did, VCid,
+};
+
+impl<T: Config> Pallet<T> {
+	/// Did of the account, the tokens are held by Dids
+	fn did_of(who: &T::AccountId) -> Result<Did, DispatchError> {
+		T::DidResolution::get_did(who).ok_or_else(|| Error::<T>::DIDDoesNotExist.into())
+	}
+}
+
+/// The TokenVC of a registered token keeps its admin, it is not pruned
+///
+/// A new version of the TokenVC keeps the currency code and the decimals of the token. Once it is
+/// active the token takes its name, and the reserve follows the change of its reservable balance.
+/// The maximum supply stays under the governance, see `set_max_supply`.
+impl<T: Config> VCLinks<T::Hash> for Pallet<T> {
+	fn is_vc_linked(vc_id: &VCid) -> bool {
+		TokenVCs::<T>::contains_key(vc_id)
+	}
+
+	fn can_supersede(vc_id: &VCid, old_vc: &VC<T::Hash>, vc: &VC<T::Hash>) -> DispatchResult {
+		if !TokenVCs::<T>::contains_key(vc_id) {
+			return Ok(())
+		}
+		let old_token_vc = T::VCResolution::decode_vc::<TokenVC>(&old_vc.vc_property)?;
+		let token_vc = T::VCResolution::decode_vc::<TokenVC>(&vc.vc_property)?;
+		ensure!(
+			token_vc.currency_code == old_token_vc.currency_code,
+			Error::<T>::CurrencyCodeMismatch
+		);
+		ensure!(token_vc.decimal == old_token_vc.decimal, Error::<T>::DecimalMismatch);
+		Ok(())
+	}
+
+	fn on_superseded(
+		vc_id: &VCid,
+		old_vc: &VC<T::Hash>,
+		by: &VCid,
+		vc: &VC<T::Hash>,
+	) -> DispatchResult {
+		let currency_code = match TokenVCs::<T>::take(vc_id) {
+			Some(currency_code) => currency_code,
+			None => return Ok(()),
+		};
+		let old_token_vc = T::VCResolution::decode_vc::<TokenVC>(&old_vc.vc_property)?;
+		let token_vc = T::VCResolution::decode_vc::<TokenVC>(&vc.vc_property)?;
+		let old_reservable: BalanceOf<T> =
+			old_token_vc.reservable_balance.try_into().map_err(|_| Error::<T>::InvalidVC)?;
+		let reservable: BalanceOf<T> =
+			token_vc.reservable_balance.try_into().map_err(|_| Error::<T>::InvalidVC)?;
+
+		Tokens::<T>::try_mutate(currency_code, |token| -> DispatchResult {
+			let token = token.as_mut().ok_or(Error::<T>::TokenNotRegistered)?;
+			if reservable > old_reservable {
+				let more = reservable.saturating_sub(old_reservable);
+				T::Currency::reserve_named(&currency_code, &token.reserve_account, more)?;
+				token.reserved = token.reserved.saturating_add(more);
+			} else {
+				// what was already withdrawn from the reserve is not given back
+				let less = old_reservable.saturating_sub(reservable).min(token.reserved);
+				T::Currency::unreserve_named(&currency_code, &token.reserve_account, less);
+				token.reserved = token.reserved.saturating_sub(less);
+			}
+			token.token_name = token_vc.token_name;
+			token.vc_id = *by;
+			Ok(())
+		})?;
+		TokenVCs::<T>::insert(by, currency_code);
+
+		Self::deposit_event(Event::TokenUpdated { currency_code, vc_id: *by });
+		Ok(())
+	}
+}
+
+/// Tokens are identified by their currency code, an account holds the tokens of its Did
+impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
+	type AssetId = CurrencyCode;
+	type Balance = BalanceOf<T>;
+
+	fn total_issuance(asset: Self::AssetId) -> Self::Balance {
+		TotalIssuance::<T>::get(asset)
+	}
+
+	fn minimum_balance(_asset: Self::AssetId) -> Self::Balance {
+		Zero::zero()
+	}
+
+	fn balance(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		T::DidResolution::get_did(who)
+			.map(|did| TokenBalances::<T>::get(asset, did))
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn reducible_balance(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		_keep_alive: bool,
+	) -> Self::Balance {
+		// tokens locked by vesting can not be moved
+		T::DidResolution::get_did(who)
+			.map(|did| {
+				let locked = VestingLocks::<T>::get(asset, did);
+				TokenBalances::<T>::get(asset, did).saturating_sub(locked)
+			})
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn can_deposit(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		mint: bool,
+	) -> DepositConsequence {
+		let token = match Tokens::<T>::get(asset) {
+			Some(token) => token,
+			None => return DepositConsequence::UnknownAsset,
+		};
+		let did = match T::DidResolution::get_did(who) {
+			Some(did) => did,
+			None => return DepositConsequence::CannotCreate,
+		};
+		if mint {
+			// minting over the maximum supply is reported as an overflow of the supply
+			match TotalIssuance::<T>::get(asset).checked_add(&amount) {
+				Some(issuance) if token.max_supply.map_or(true, |max| issuance <= max) => {},
+				_ => return DepositConsequence::Overflow,
+			}
+		}
+		if TokenBalances::<T>::get(asset, did).checked_add(&amount).is_none() {
+			return DepositConsequence::Overflow
+		}
+		DepositConsequence::Success
+	}
+
+	fn can_withdraw(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if !Tokens::<T>::contains_key(asset) {
+			return WithdrawConsequence::UnknownAsset
+		}
+		if amount.is_zero() {
+			return WithdrawConsequence::Success
+		}
+		let frozen = T::DidResolution::get_did(who)
+			.map_or(false, |did| FrozenHoldings::<T>::contains_key(asset, did));
+		if frozen {
+			return WithdrawConsequence::Frozen
+		}
+		if Self::balance(asset, who) < amount {
+			return WithdrawConsequence::NoFunds
+		}
+		if Self::reducible_balance(asset, who, false) < amount {
+			return WithdrawConsequence::Frozen
+		}
+		WithdrawConsequence::Success
+	}
+}
+
+impl<T: Config> fungibles::Mutate<T::AccountId> for Pallet<T> {
+	fn mint_into(asset: Self::AssetId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		Self::do_mint(asset, Self::did_of(who)?, amount)
+	}
+
+	fn burn_from(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::do_burn(asset, Self::did_of(who)?, amount)?;
+		Ok(amount)
+	}
+}
+
+impl<T: Config> fungibles::Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(
+		asset: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		_keep_alive: bool,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::do_transfer(asset, Self::did_of(source)?, Self::did_of(dest)?, amount)?;
+		Ok(amount)
+	}
+}
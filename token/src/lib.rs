@@ -4,16 +4,20 @@ use crate::types::*;
 use frame_support::{
 	fail,
 	pallet_prelude::*,
-	sp_runtime::DispatchError,
+	sp_runtime::{
+		traits::{CheckedAdd, CheckedSub, SaturatedConversion, Saturating, Zero},
+		DispatchError,
+	},
 	traits::{
-		Currency as PalletCurrency, ExistenceRequirement, LockableCurrency, OnKilledAccount,
-		OnNewAccount, ReservableCurrency, StoredMap,
+		BalanceStatus, Currency as PalletCurrency, NamedReservableCurrency, OnKilledAccount,
+		OnNewAccount, StoredMap,
 	},
 };
+use frame_support::sp_std::prelude::*;
 use frame_system::{pallet_prelude::*, split_inner};
 use metamui_primitives::{
 	traits::{DidResolve, HasVCId, MultiAddress, VCResolve},
-	types::{SlashMintTokens, TokenTransferVC, VCType, VC},
+	types::{CurrencyCode, SlashMintTokens, TokenTransferVC, TokenVC, VCType, VC},
 	Did, VCid,
 };
 pub use pallet::*;
@@ -26,14 +30,24 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod types;
+mod impls;
+pub mod migrations;
+pub mod types;
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	type BalanceOf<T> = <<T as Config>::Currency as PalletCurrency<
+	use frame_support::traits::GetStorageVersion;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	pub(crate) type BalanceOf<T> = <<T as Config>::Currency as PalletCurrency<
 		<T as frame_system::Config>::AccountId,
 	>>::Balance;
+	pub(crate) type VestingScheduleOf<T> =
+		VestingSchedule<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub(crate) type EscrowOf<T> = Escrow<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
@@ -42,16 +56,28 @@ pub mod pallet {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		/// Validator Origin
 		type WithdrawOrigin: EnsureOrigin<Self::Origin>;
-		/// The staking balance.
-		type Currency: LockableCurrency<Self::AccountId> + ReservableCurrency<Self::AccountId>;
+		/// Origin allowed to govern the tokens, e.g. the council
+		type GovernanceOrigin: EnsureOrigin<Self::Origin>;
+		/// The staking balance, the reserve of a token is named by its currency code.
+		type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = CurrencyCode>;
 		/// Resolve Did from account Id
 		type DidResolution: DidResolve<Self::AccountId>;
 		/// Resolve VC Data
 		type VCResolution: VCResolve<Self::Hash>;
+		/// Maximum number of vesting schedules of a Did for a token
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+		/// Maximum number of open escrows sent by a Did
+		#[pallet::constant]
+		type MaxEscrows: Get<u32>;
+		/// Maximum number of escrows with the same deadline
+		#[pallet::constant]
+		type MaxEscrowExpiries: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -60,11 +86,162 @@ pub mod pallet {
 	pub type Account<T: Config> =
 		StorageMap<_, Blake2_128Concat, Did, AccountInfo<T::Index, T::AccountData>, ValueQuery>;
 
+	/// The tokens registered from a `TokenVC`, by currency code
+	#[pallet::storage]
+	#[pallet::getter(fn token)]
+	pub type Tokens<T: Config> =
+		StorageMap<_, Blake2_128Concat, CurrencyCode, TokenInfo<T::AccountId, BalanceOf<T>>, OptionQuery>;
+
+	/// The token each registered `TokenVC` is linked to, a linked TokenVC is not pruned
+	#[pallet::storage]
+	#[pallet::getter(fn token_vc)]
+	pub type TokenVCs<T: Config> =
+		StorageMap<_, Blake2_128Concat, VCid, CurrencyCode, OptionQuery>;
+
+	/// Balance of each Did in each token
+	#[pallet::storage]
+	#[pallet::getter(fn token_balance)]
+	pub type TokenBalances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyCode,
+		Blake2_128Concat,
+		Did,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Currency codes of the tokens each Did has a free or held balance of
+	#[pallet::storage]
+	pub type HolderTokens<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Did, Blake2_128Concat, CurrencyCode, (), OptionQuery>;
+
+	/// Amount of each token held from the Dids, e.g. for pending redemptions
+	#[pallet::storage]
+	#[pallet::getter(fn held_balance)]
+	pub type HeldBalances<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyCode,
+		Blake2_128Concat,
+		Did,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Circulating supply of each token, the amount minted less the amount burned
+	///
+	/// Held tokens are part of the circulating supply until they are burned.
+	#[pallet::storage]
+	#[pallet::getter(fn total_issuance)]
+	pub type TotalIssuance<T: Config> =
+		StorageMap<_, Blake2_128Concat, CurrencyCode, BalanceOf<T>, ValueQuery>;
+
+	/// Holdings frozen by the token owner or the governance, with the reason they were frozen
+	#[pallet::storage]
+	#[pallet::getter(fn frozen)]
+	pub type FrozenHoldings<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyCode,
+		Blake2_128Concat,
+		Did,
+		ReasonCode,
+		OptionQuery,
+	>;
+
+	/// Vesting schedules of the tokens of each Did
+	#[pallet::storage]
+	#[pallet::getter(fn vesting)]
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyCode,
+		Blake2_128Concat,
+		Did,
+		BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules>,
+		ValueQuery,
+	>;
+
+	/// Amount of each token locked from the Dids by their vesting schedules
+	///
+	/// The tokens are kept in `TokenBalances`, not in `T::Currency`, so a lock of
+	/// `LockableCurrency` can not hold them. The locked amount can not be transferred or burned
+	/// out of the balance of the token.
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_locked)]
+	pub type VestingLocks<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyCode,
+		Blake2_128Concat,
+		Did,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Id of the next redemption request
+	#[pallet::storage]
+	pub type NextRedemptionId<T: Config> = StorageValue<_, RedemptionId, ValueQuery>;
+
+	/// Redemption requests of the holders waiting for the issuer, by id
+	///
+	/// A request is removed once it is settled or rejected, the events record the outcome.
+	#[pallet::storage]
+	#[pallet::getter(fn redemption)]
+	pub type Redemptions<T: Config> =
+		StorageMap<_, Blake2_128Concat, RedemptionId, Redemption<BalanceOf<T>>, OptionQuery>;
+
+	/// Amount of a token the owner Did allows the spender Did to transfer from its balance
+	#[pallet::storage]
+	#[pallet::getter(fn allowance)]
+	pub type Allowances<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, Did>,
+			NMapKey<Blake2_128Concat, Did>,
+			NMapKey<Blake2_128Concat, CurrencyCode>,
+		),
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Id of the next escrow
+	#[pallet::storage]
+	pub type NextEscrowId<T: Config> = StorageValue<_, EscrowId, ValueQuery>;
+
+	/// Open escrows, by id
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub type Escrows<T: Config> =
+		StorageMap<_, Blake2_128Concat, EscrowId, EscrowOf<T>, OptionQuery>;
+
+	/// Ids of the open escrows sent by each Did
+	#[pallet::storage]
+	#[pallet::getter(fn escrows_of)]
+	pub type EscrowsOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, Did, BoundedVec<EscrowId, T::MaxEscrows>, ValueQuery>;
+
+	/// Ids of the open escrows refunded at each block
+	#[pallet::storage]
+	pub type EscrowExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<EscrowId, T::MaxEscrowExpiries>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Withdrawn reserve from account
-		ReserveWithdrawn { from: Did, to: Did },
+		/// Withdrawn reserve of a token from account
+		ReserveWithdrawn {
+			currency_code: CurrencyCode,
+			from: Did,
+			to: Did,
+			amount: BalanceOf<T>,
+		},
 		/// Token amount is slashed
 		TokenSlashed { balance: BalanceOf<T>, vc_id: VCid },
 		/// Token amount is minted
@@ -73,6 +250,80 @@ pub mod pallet {
 		TransferredWithVC { to: Did, balance: BalanceOf<T>, vc_id: VCid },
 		/// Provider count increased
 		ProviderIncreased {did: Did, account: T::AccountId },
+		/// A token is registered from its TokenVC
+		TokenRegistered { currency_code: CurrencyCode, issuer: Did, vc_id: VCid },
+		/// A token follows the new version of its TokenVC
+		TokenUpdated { currency_code: CurrencyCode, vc_id: VCid },
+		/// The maximum supply of a token is changed
+		MaxSupplySet { currency_code: CurrencyCode, max_supply: Option<BalanceOf<T>> },
+		/// The holding of the token by the Did is frozen
+		Frozen { currency_code: CurrencyCode, did: Did, reason: ReasonCode },
+		/// The holding of the token by the Did is thawed
+		Thawed { currency_code: CurrencyCode, did: Did, reason: ReasonCode },
+		/// A holder requested to redeem tokens, the tokens are held until the request is closed
+		RedemptionRequested {
+			id: RedemptionId,
+			currency_code: CurrencyCode,
+			holder: Did,
+			amount: BalanceOf<T>,
+		},
+		/// The issuer settled a redemption, the held tokens are burned
+		RedemptionSettled {
+			id: RedemptionId,
+			currency_code: CurrencyCode,
+			holder: Did,
+			amount: BalanceOf<T>,
+		},
+		/// The issuer rejected a redemption, the held tokens are returned to the holder
+		RedemptionRejected {
+			id: RedemptionId,
+			currency_code: CurrencyCode,
+			holder: Did,
+			amount: BalanceOf<T>,
+			reason: ReasonCode,
+		},
+		/// Tokens are transferred to the Did under a vesting schedule
+		VestingScheduleAdded { currency_code: CurrencyCode, did: Did, amount: BalanceOf<T> },
+		/// Vested tokens of the Did are unlocked
+		VestingClaimed { currency_code: CurrencyCode, did: Did, amount: BalanceOf<T> },
+		/// Tokens are held from the sender until they are released to the recipient or refunded
+		EscrowCreated {
+			id: EscrowId,
+			currency_code: CurrencyCode,
+			sender: Did,
+			recipient: Did,
+			amount: BalanceOf<T>,
+			deadline: T::BlockNumber,
+		},
+		/// The escrowed tokens are released to the recipient
+		EscrowReleased {
+			id: EscrowId,
+			currency_code: CurrencyCode,
+			recipient: Did,
+			amount: BalanceOf<T>,
+		},
+		/// The escrowed tokens are refunded to the sender
+		EscrowRefunded {
+			id: EscrowId,
+			currency_code: CurrencyCode,
+			sender: Did,
+			amount: BalanceOf<T>,
+		},
+		/// The allowance of the spender on the tokens of the owner is changed
+		AllowanceSet {
+			currency_code: CurrencyCode,
+			owner: Did,
+			spender: Did,
+			amount: BalanceOf<T>,
+		},
+		/// The spender transferred tokens of the owner
+		TransferredFrom {
+			currency_code: CurrencyCode,
+			owner: Did,
+			spender: Did,
+			to: Did,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -96,47 +347,125 @@ pub mod pallet {
 		VCAlreadyUsed,
 		/// Recipent DID Not Registered
 		RecipentDIDNotRegistered,
+		/// A token with the currency code is already registered
+		TokenAlreadyRegistered,
+		/// No token is registered with the currency code
+		TokenNotRegistered,
+		/// The currency code is registered for a token of another TokenVC, or a new version of
+		/// the TokenVC changes it
+		CurrencyCodeMismatch,
+		/// The balance of the token would overflow
+		BalanceOverflow,
+		/// Minting would take the circulating supply over the maximum supply of the token
+		MaxSupplyExceeded,
+		/// The maximum supply is below the circulating supply of the token
+		MaxSupplyBelowIssuance,
+		/// The sender is not the owner of the TokenVC of the token
+		NotTokenOwner,
+		/// The holding of the token is frozen
+		HoldingFrozen,
+		/// The holding of the token is already frozen
+		AlreadyFrozen,
+		/// The holding of the token is not frozen
+		NotFrozen,
+		/// The amount must be greater than zero
+		ZeroAmount,
+		/// No pending redemption request exists with the id
+		RedemptionNotFound,
+		/// The amount is more than what is left of the reserve of the token
+		ReserveTooLow,
+		/// The tokens are locked by a vesting schedule
+		BalanceLocked,
+		/// The cliff of the vesting schedule is after its end
+		InvalidVestingSchedule,
+		/// The Did has the maximum number of vesting schedules for the token
+		TooManyVestingSchedules,
+		/// No vested tokens are left to claim
+		NothingToClaim,
+		/// No open escrow exists with the id
+		EscrowNotFound,
+		/// The sender has the maximum number of open escrows
+		TooManyEscrows,
+		/// The maximum number of escrows expire at the deadline
+		TooManyEscrowExpiries,
+		/// The deadline of the escrow is not in the future
+		InvalidDeadline,
+		/// The sender is not allowed to settle the escrow this way
+		NotAllowedToSettle,
+		/// The amount is more than the allowance of the spender
+		AllowanceTooLow,
+		/// The new version of a TokenVC changes the decimals of its token
+		DecimalMismatch,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			let mut weight = T::DbWeight::get().reads(1);
+			if on_chain_version < 1 {
+				weight += migrations::v1::migrate::<T>();
+			}
+			weight
+		}
+
+		/// Refund the escrows reaching their deadline
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ids = EscrowExpiries::<T>::take(now);
+			for id in ids.iter() {
+				if let Some(escrow) = Escrows::<T>::get(id) {
+					// an escrow that can not be refunded stays open, its sender can refund it later
+					let _ = Self::settle_escrow(*id, escrow, false);
+				}
+			}
+			let per_escrow = T::DbWeight::get().reads_writes(4, 4);
+			T::DbWeight::get()
+				.reads_writes(1, 1)
+				.saturating_add(per_escrow.saturating_mul(ids.len() as Weight))
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		// Transfer to admin from reserved amount for operational costs
+		// Transfer to admin from the amount reserved for a token for operational costs
 		// The dispatch origin for this call must be `Signed` by a validator account.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		// At most what is left of the reserve made when the token was registered is withdrawn,
+		// from the account the reserve was made from.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 3))]
 		pub fn withdraw_reserved(
 			origin: OriginFor<T>,
 			to: Did,
-			from: Did,
+			currency_code: CurrencyCode,
 			amount: BalanceOf<T>,
 		) -> DispatchResult {
 			let _ = T::WithdrawOrigin::ensure_origin(origin)?;
-			ensure!(
-				T::DidResolution::did_exists(MultiAddress::Did(to)),
-				Error::<T>::DIDDoesNotExist
-			);
-			ensure!(
-				T::DidResolution::did_exists(MultiAddress::Did(from)),
-				Error::<T>::DIDDoesNotExist
-			);
+			let to_acc =
+				T::DidResolution::get_account_id(&to).ok_or(Error::<T>::DIDDoesNotExist)?;
 
-			let from_acc = T::DidResolution::get_account_id(&from).unwrap();
-			let to_acc = T::DidResolution::get_account_id(&to).unwrap();
+			let mut token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			token.reserved = token.reserved.checked_sub(&amount).ok_or(Error::<T>::ReserveTooLow)?;
 
-			// unreserve the mui balance required to issue new token
-			T::Currency::unreserve(&from_acc, amount);
-			// transfer amount to destination
-			T::Currency::transfer(&from_acc, &to_acc, amount, ExistenceRequirement::KeepAlive)?;
+			// move the mui balance reserved to issue the token to the destination
+			let leftover = T::Currency::repatriate_reserved_named(
+				&currency_code,
+				&token.reserve_account,
+				&to_acc,
+				amount,
+				BalanceStatus::Free,
+			)?;
+			ensure!(leftover.is_zero(), Error::<T>::ReserveTooLow);
+			let from = token.issuer;
+			Tokens::<T>::insert(currency_code, token);
 
-			Self::deposit_event(Event::ReserveWithdrawn { from, to });
+			Self::deposit_event(Event::ReserveWithdrawn { currency_code, from, to, amount });
 
 			Ok(())
 		}
 
 		/// Slash the balance from the issuer account
 		///
+		/// The token is the one registered with the currency code of the VC.
+		///
 		/// The dispatch origin for this call must be `Signed` by a issuer account.
 		#[pallet::weight(1)]
 		pub fn slash_token(origin: OriginFor<T>, vc_id: VCid) -> DispatchResultWithPostInfo {
@@ -149,23 +478,29 @@ pub mod pallet {
 				T::VCResolution::decode_vc::<SlashMintTokens>(&vc_struct.vc_property)?;
 
 			let amount: BalanceOf<T> = slash_vc.amount.try_into().ok().unwrap_or_default();
-			let vc_owner = Self::get_vc_owner::<SlashMintTokens>(vc_struct)?;
+			let currency_code = slash_vc.currency_code;
+			let vc_owner = Self::get_vc_owner(&slash_vc, &currency_code)?;
 
-			ensure!(T::Currency::can_slash(&vc_owner, amount), Error::<T>::BalanceTooLow);
-			
-			T::Currency::slash(&vc_owner, amount);
-			// update vc's is_used flag as used
-			T::VCResolution::set_is_vc_used(&vc_id, true);
+			Self::do_burn(currency_code, vc_owner, amount)?;
+			// consume one use of the vc
+			T::VCResolution::consume_vc(&vc_id)?;
 
 			Self::deposit_event(Event::TokenSlashed { balance: amount, vc_id });
 
 			Ok(().into())
 		}
+		/// Transfer part of the amount in the transfer vc to the recipient
+		///
+		/// The token is the one registered with the currency code of the VC.
+		///
+		/// The vc is marked used only when its amount is exhausted. Tokens can not be
+		/// transferred out of a frozen holding.
 		#[pallet::weight(1)]
 		pub fn transfer_token(
 			origin: OriginFor<T>,
 			vc_id: VCid,
 			to: Did,
+			amount: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
@@ -179,14 +514,13 @@ pub mod pallet {
 
 			let transfer_vc: TokenTransferVC =
 				T::VCResolution::decode_vc::<TokenTransferVC>(&vc_struct.vc_property)?;
+			let currency_code = transfer_vc.currency_code;
+			let vc_owner = Self::get_vc_owner(&transfer_vc, &currency_code)?;
 
-			let amount: BalanceOf<T> = transfer_vc.amount.try_into().ok().unwrap_or_default();
-			let vc_owner = Self::get_vc_owner::<TokenTransferVC>(vc_struct)?;
-			let to_acc = T::DidResolution::get_account_id(&to).unwrap();
+			// draw the amount from what is left in the vc
+			T::VCResolution::consume_vc_amount(&vc_id, amount.saturated_into::<u128>())?;
 
-			T::Currency::transfer(&vc_owner, &to_acc, amount, ExistenceRequirement::KeepAlive)?;
-			// update vc's is_used flag as used
-			T::VCResolution::set_is_vc_used(&vc_id, true);
+			Self::do_transfer(currency_code, vc_owner, to, amount)?;
 
 			Self::deposit_event(Event::TransferredWithVC { to, balance: amount, vc_id });
 
@@ -195,10 +529,16 @@ pub mod pallet {
 
 		/// Add amount to the issuer account
 		///
+		/// The token is the one registered with the currency code of the VC.
+		///
 		/// The dispatch origin for this call must be `Signed` by a issuer account.
-		/// Sender must be part of vc
+		/// Sender must be part of vc, the amount can be up to what is left in the vc
 		#[pallet::weight(1)]
-		pub fn mint_token(origin: OriginFor<T>, vc_id: VCid) -> DispatchResultWithPostInfo {
+		pub fn mint_token(
+			origin: OriginFor<T>,
+			vc_id: VCid,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
 			let vc_struct =
@@ -206,19 +546,518 @@ pub mod pallet {
 
 			let mint_vc: SlashMintTokens =
 				T::VCResolution::decode_vc::<SlashMintTokens>(&vc_struct.vc_property)?;
+			let currency_code = mint_vc.currency_code;
+			let vc_owner = Self::get_vc_owner(&mint_vc, &currency_code)?;
 
-			let amount: BalanceOf<T> = mint_vc.amount.try_into().ok().unwrap_or_default();
-			let vc_owner = Self::get_vc_owner::<SlashMintTokens>(vc_struct)?;
+			// draw the amount from what is left in the vc
+			T::VCResolution::consume_vc_amount(&vc_id, amount.saturated_into::<u128>())?;
 
-			T::Currency::deposit_creating(&vc_owner, amount);
-			// update vc's is_used flag as used
-			T::VCResolution::set_is_vc_used(&vc_id, true);
+			Self::do_mint(currency_code, vc_owner, amount)?;
 
 			Self::deposit_event(Event::TokenMinted { balance: amount, vc_id });
 
 			Ok(().into())
 		}
 
+		/// Register the token described by a TokenVC
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the TokenVC. The
+		/// reservable balance of the VC is reserved from the owner, and the currency code can
+		/// not be registered again. One use of the TokenVC is consumed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7, 5))]
+		pub fn register_token(origin: OriginFor<T>, vc_id: VCid) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let vc_struct =
+				Self::validate_vc(&sender, vc_id, VCType::TokenVC, Error::<T>::InvalidVC)?;
+			let token_vc: TokenVC = T::VCResolution::decode_vc::<TokenVC>(&vc_struct.vc_property)?;
+			let currency_code = token_vc.currency_code;
+			ensure!(!Tokens::<T>::contains_key(currency_code), Error::<T>::TokenAlreadyRegistered);
+
+			let issuer = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+			let reserved: BalanceOf<T> =
+				token_vc.reservable_balance.try_into().map_err(|_| Error::<T>::InvalidVC)?;
+			let max_supply = token_vc
+				.max_supply
+				.map(|max_supply| max_supply.try_into().map_err(|_| Error::<T>::InvalidVC))
+				.transpose()?;
+			T::Currency::reserve_named(&currency_code, &sender, reserved)?;
+			// consume one use of the vc
+			T::VCResolution::consume_vc(&vc_id)?;
+
+			Tokens::<T>::insert(
+				currency_code,
+				TokenInfo {
+					token_name: token_vc.token_name,
+					decimal: token_vc.decimal,
+					issuer,
+					vc_id,
+					reserve_account: sender,
+					reserved,
+					total_minted: Default::default(),
+					total_burned: Default::default(),
+					max_supply,
+				},
+			);
+
+			TokenVCs::<T>::insert(vc_id, currency_code);
+
+			Self::deposit_event(Event::TokenRegistered { currency_code, issuer, vc_id });
+
+			Ok(().into())
+		}
+
+		/// Set the maximum supply of a token, `None` removes the cap
+		///
+		/// The dispatch origin for this call must be the `GovernanceOrigin`. The cap can not be
+		/// set below the circulating supply.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn set_max_supply(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			max_supply: Option<BalanceOf<T>>,
+		) -> DispatchResultWithPostInfo {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+
+			Tokens::<T>::try_mutate(currency_code, |token| -> DispatchResult {
+				let token = token.as_mut().ok_or(Error::<T>::TokenNotRegistered)?;
+				if let Some(max_supply) = max_supply {
+					ensure!(
+						TotalIssuance::<T>::get(currency_code) <= max_supply,
+						Error::<T>::MaxSupplyBelowIssuance
+					);
+				}
+				token.max_supply = max_supply;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MaxSupplySet { currency_code, max_supply });
+
+			Ok(().into())
+		}
+
+		/// Freeze the holding of a token by the Did, tokens can not be transferred out of it
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the TokenVC of the
+		/// token, or be the `GovernanceOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 1))]
+		pub fn freeze(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			did: Did,
+			reason: ReasonCode,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_token_admin(origin, &currency_code)?;
+			ensure!(
+				T::DidResolution::did_exists(MultiAddress::Did(did)),
+				Error::<T>::DIDDoesNotExist
+			);
+			ensure!(
+				!FrozenHoldings::<T>::contains_key(currency_code, did),
+				Error::<T>::AlreadyFrozen
+			);
+
+			FrozenHoldings::<T>::insert(currency_code, did, reason);
+
+			Self::deposit_event(Event::Frozen { currency_code, did, reason });
+
+			Ok(().into())
+		}
+
+		/// Thaw a frozen holding of a token
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the TokenVC of the
+		/// token, or be the `GovernanceOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn thaw(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			did: Did,
+			reason: ReasonCode,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_token_admin(origin, &currency_code)?;
+			ensure!(FrozenHoldings::<T>::contains_key(currency_code, did), Error::<T>::NotFrozen);
+
+			FrozenHoldings::<T>::remove(currency_code, did);
+
+			Self::deposit_event(Event::Thawed { currency_code, did, reason });
+
+			Ok(().into())
+		}
+
+		/// Request the redemption of tokens with the issuer
+		///
+		/// The dispatch origin for this call must be `Signed` by the holder of the tokens. The
+		/// amount is held from the balance of the holder until the issuer settles or rejects the
+		/// request.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn request_redemption(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let holder = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+			Self::do_hold(currency_code, holder, amount)?;
+
+			let id = NextRedemptionId::<T>::get();
+			NextRedemptionId::<T>::put(id.saturating_add(1));
+			Redemptions::<T>::insert(
+				id,
+				Redemption { currency_code, holder, amount },
+			);
+
+			Self::deposit_event(Event::RedemptionRequested { id, currency_code, holder, amount });
+
+			Ok(().into())
+		}
+
+		/// Settle a pending redemption once it is paid out, the held tokens are burned and the
+		/// request is removed
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the TokenVC of the
+		/// token, or be the `GovernanceOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn settle_redemption(
+			origin: OriginFor<T>,
+			id: RedemptionId,
+		) -> DispatchResultWithPostInfo {
+			let redemption = Self::pending_redemption(id)?;
+			Self::ensure_token_admin(origin, &redemption.currency_code)?;
+
+			let Redemption { currency_code, holder, amount } = redemption;
+			Self::do_burn_held(currency_code, holder, amount)?;
+			Redemptions::<T>::remove(id);
+
+			Self::deposit_event(Event::RedemptionSettled { id, currency_code, holder, amount });
+
+			Ok(().into())
+		}
+
+		/// Reject a pending redemption, the held tokens are returned to the holder and the request
+		/// is removed
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the TokenVC of the
+		/// token, or be the `GovernanceOrigin`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 3))]
+		pub fn reject_redemption(
+			origin: OriginFor<T>,
+			id: RedemptionId,
+			reason: ReasonCode,
+		) -> DispatchResultWithPostInfo {
+			let redemption = Self::pending_redemption(id)?;
+			Self::ensure_token_admin(origin, &redemption.currency_code)?;
+
+			let Redemption { currency_code, holder, amount } = redemption;
+			Self::do_release(currency_code, holder, amount)?;
+			Redemptions::<T>::remove(id);
+
+			Self::deposit_event(Event::RedemptionRejected {
+				id,
+				currency_code,
+				holder,
+				amount,
+				reason,
+			});
+
+			Ok(().into())
+		}
+
+		/// Transfer tokens to the Did, locked under a vesting schedule
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the TokenVC of the
+		/// token. The tokens vest linearly over `duration` blocks from `start`, nothing vests
+		/// before `cliff` blocks have passed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7, 4))]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			to: Did,
+			amount: BalanceOf<T>,
+			start: T::BlockNumber,
+			cliff: T::BlockNumber,
+			duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			let owner = T::VCResolution::get_vc_owner(&token.vc_id);
+			let sender_did = T::DidResolution::get_did(&sender);
+			ensure!(sender_did.is_some() && sender_did == owner, Error::<T>::NotTokenOwner);
+			let from = sender_did.ok_or(Error::<T>::DIDDoesNotExist)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(cliff <= duration, Error::<T>::InvalidVestingSchedule);
+			ensure!(
+				T::DidResolution::did_exists(MultiAddress::Did(to)),
+				Error::<T>::RecipentDIDNotRegistered
+			);
+
+			let schedule =
+				VestingSchedule { amount, start, cliff, duration, claimed: Zero::zero() };
+			VestingSchedules::<T>::try_mutate(currency_code, to, |schedules| {
+				schedules.try_push(schedule).map_err(|_| Error::<T>::TooManyVestingSchedules)
+			})?;
+			Self::do_transfer(currency_code, from, to, amount)?;
+			VestingLocks::<T>::try_mutate(currency_code, to, |locked| -> DispatchResult {
+				*locked = locked.checked_add(&amount).ok_or(Error::<T>::BalanceOverflow)?;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::VestingScheduleAdded { currency_code, did: to, amount });
+
+			Ok(().into())
+		}
+
+		/// Unlock the vested tokens of the sender
+		///
+		/// The dispatch origin for this call must be `Signed` by the holder of the tokens.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(3, 2) +
+				T::DbWeight::get().reads(T::MaxVestingSchedules::get().into())
+		)]
+		pub fn claim_vested(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let did = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut schedules = VestingSchedules::<T>::get(currency_code, did);
+			let mut amount: BalanceOf<T> = Zero::zero();
+			for schedule in schedules.iter_mut() {
+				let vested = schedule.vested_at(now);
+				amount = amount.saturating_add(vested.saturating_sub(schedule.claimed));
+				schedule.claimed = vested;
+			}
+			ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+			// schedules are dropped once all their tokens are claimed
+			schedules.retain(|schedule| !schedule.locked().is_zero());
+			if schedules.is_empty() {
+				VestingSchedules::<T>::remove(currency_code, did);
+			} else {
+				VestingSchedules::<T>::insert(currency_code, did, schedules);
+			}
+			let locked = VestingLocks::<T>::get(currency_code, did).saturating_sub(amount);
+			if locked.is_zero() {
+				VestingLocks::<T>::remove(currency_code, did);
+			} else {
+				VestingLocks::<T>::insert(currency_code, did, locked);
+			}
+
+			Self::deposit_event(Event::VestingClaimed { currency_code, did, amount });
+
+			Ok(().into())
+		}
+
+		/// Hold tokens of the sender for the recipient until the deadline
+		///
+		/// The dispatch origin for this call must be `Signed` by the holder of the tokens. The
+		/// recipient or the arbiter releases the tokens to the recipient, otherwise they are
+		/// refunded to the sender at the deadline.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 6))]
+		pub fn create_escrow(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			recipient: Did,
+			arbiter: Option<Did>,
+			amount: BalanceOf<T>,
+			deadline: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let sender = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			ensure!(
+				deadline > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidDeadline
+			);
+			ensure!(
+				T::DidResolution::did_exists(MultiAddress::Did(recipient)),
+				Error::<T>::RecipentDIDNotRegistered
+			);
+
+			let id = NextEscrowId::<T>::get();
+			NextEscrowId::<T>::put(id.saturating_add(1));
+			EscrowsOf::<T>::try_mutate(sender, |ids| ids.try_push(id))
+				.map_err(|_| Error::<T>::TooManyEscrows)?;
+			EscrowExpiries::<T>::try_mutate(deadline, |ids| ids.try_push(id))
+				.map_err(|_| Error::<T>::TooManyEscrowExpiries)?;
+			Self::do_hold(currency_code, sender, amount)?;
+			Escrows::<T>::insert(
+				id,
+				Escrow { currency_code, sender, recipient, arbiter, amount, deadline },
+			);
+
+			Self::deposit_event(Event::EscrowCreated {
+				id,
+				currency_code,
+				sender,
+				recipient,
+				amount,
+				deadline,
+			});
+
+			Ok(().into())
+		}
+
+		/// Release the escrowed tokens to the recipient
+		///
+		/// The dispatch origin for this call must be `Signed` by the recipient or the arbiter of
+		/// the escrow. The tokens are not released while the holding of the sender or of the
+		/// recipient is frozen.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 6))]
+		pub fn release_escrow(origin: OriginFor<T>, id: EscrowId) -> DispatchResultWithPostInfo {
+			let (did, escrow) = Self::escrow_party(origin, id)?;
+			ensure!(
+				did == escrow.recipient || Some(did) == escrow.arbiter,
+				Error::<T>::NotAllowedToSettle
+			);
+
+			Self::remove_expiry(escrow.deadline, id);
+			Self::settle_escrow(id, escrow, true)?;
+
+			Ok(().into())
+		}
+
+		/// Refund the escrowed tokens to the sender
+		///
+		/// The dispatch origin for this call must be `Signed` by the recipient, who declines the
+		/// tokens, or by the sender once the deadline is reached.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 6))]
+		pub fn refund_escrow(origin: OriginFor<T>, id: EscrowId) -> DispatchResultWithPostInfo {
+			let (did, escrow) = Self::escrow_party(origin, id)?;
+			let expired = frame_system::Pallet::<T>::block_number() >= escrow.deadline;
+			ensure!(
+				did == escrow.recipient || (did == escrow.sender && expired),
+				Error::<T>::NotAllowedToSettle
+			);
+
+			Self::remove_expiry(escrow.deadline, id);
+			Self::settle_escrow(id, escrow, false)?;
+
+			Ok(().into())
+		}
+
+		/// Settle the escrow either way, `release` pays the recipient and otherwise the sender is
+		/// refunded
+		///
+		/// The dispatch origin for this call must be `Signed` by the arbiter of the escrow.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(8, 6))]
+		pub fn arbitrate_escrow(
+			origin: OriginFor<T>,
+			id: EscrowId,
+			release: bool,
+		) -> DispatchResultWithPostInfo {
+			let (did, escrow) = Self::escrow_party(origin, id)?;
+			ensure!(Some(did) == escrow.arbiter, Error::<T>::NotAllowedToSettle);
+
+			Self::remove_expiry(escrow.deadline, id);
+			Self::settle_escrow(id, escrow, release)?;
+
+			Ok(().into())
+		}
+
+		/// Allow the spender to transfer up to the amount of the token from the sender
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the tokens. The
+		/// amount replaces the current allowance of the spender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		pub fn approve(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			spender: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = Self::allowance_owner(origin, &currency_code, &spender)?;
+
+			Self::set_allowance(currency_code, owner, spender, amount);
+
+			Ok(().into())
+		}
+
+		/// Raise the allowance of the spender on the token of the sender
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the tokens.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn increase_allowance(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			spender: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = Self::allowance_owner(origin, &currency_code, &spender)?;
+
+			let allowance = Allowances::<T>::get((owner, spender, currency_code))
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+			Self::set_allowance(currency_code, owner, spender, allowance);
+
+			Ok(().into())
+		}
+
+		/// Lower the allowance of the spender on the token of the sender
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner of the tokens.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 1))]
+		pub fn decrease_allowance(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			spender: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = Self::allowance_owner(origin, &currency_code, &spender)?;
+
+			let allowance = Allowances::<T>::get((owner, spender, currency_code))
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::AllowanceTooLow)?;
+			Self::set_allowance(currency_code, owner, spender, allowance);
+
+			Ok(().into())
+		}
+
+		/// Transfer tokens of the owner within the allowance of the sender
+		///
+		/// The dispatch origin for this call must be `Signed` by the spender, with the key of
+		/// its own Did.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(7, 3))]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			currency_code: CurrencyCode,
+			owner: Did,
+			to: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let spender = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+			ensure!(
+				T::DidResolution::did_exists(MultiAddress::Did(to)),
+				Error::<T>::RecipentDIDNotRegistered
+			);
+
+			let allowance = Allowances::<T>::get((owner, spender, currency_code))
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::AllowanceTooLow)?;
+			Self::do_transfer(currency_code, owner, to, amount)?;
+			if allowance.is_zero() {
+				Allowances::<T>::remove((owner, spender, currency_code));
+			} else {
+				Allowances::<T>::insert((owner, spender, currency_code), allowance);
+			}
+
+			Self::deposit_event(Event::TransferredFrom {
+				currency_code,
+				owner,
+				spender,
+				to,
+				amount,
+			});
+
+			Ok(().into())
+		}
+
 		#[pallet::weight(10_000)]
 		pub fn inc_provider(origin: OriginFor<T>, did: Did) -> DispatchResultWithPostInfo {
 			ensure_root(origin)?;
@@ -245,6 +1084,59 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// The token registered with the currency code
+		pub fn token_info(
+			currency_code: CurrencyCode,
+		) -> Option<TokenInfo<T::AccountId, BalanceOf<T>>> {
+			Tokens::<T>::get(currency_code)
+		}
+
+		/// Free and held balances of the tokens the Did holds
+		pub fn balances_of(did: Did) -> Vec<(CurrencyCode, BalanceOf<T>, BalanceOf<T>)> {
+			HolderTokens::<T>::iter_key_prefix(did)
+				.map(|currency_code| {
+					(
+						currency_code,
+						TokenBalances::<T>::get(currency_code, did),
+						HeldBalances::<T>::get(currency_code, did),
+					)
+				})
+				.collect()
+		}
+
+		/// Holders of the token with their free and held balances, at most `limit` of them
+		/// starting at `offset`
+		///
+		/// The holders with a free balance come first, then the ones with only a held balance.
+		pub fn holders(
+			currency_code: CurrencyCode,
+			offset: u32,
+			limit: u32,
+		) -> Vec<(Did, BalanceOf<T>, BalanceOf<T>)> {
+			let with_free = TokenBalances::<T>::iter_prefix(currency_code).map(|(did, balance)| {
+				(did, balance, HeldBalances::<T>::get(currency_code, did))
+			});
+			let only_held = HeldBalances::<T>::iter_prefix(currency_code)
+				.filter(|(did, _)| !TokenBalances::<T>::contains_key(currency_code, did))
+				.map(|(did, held)| (did, Zero::zero(), held));
+			with_free.chain(only_held).skip(offset as usize).take(limit as usize).collect()
+		}
+
+		/// Amounts of the token of the Did locked by vesting, and vested but not claimed yet
+		pub fn vesting_balance(
+			currency_code: CurrencyCode,
+			did: Did,
+		) -> VestingBalance<BalanceOf<T>> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let vested = VestingSchedules::<T>::get(currency_code, did).iter().fold(
+				Zero::zero(),
+				|vested: BalanceOf<T>, schedule| {
+					vested.saturating_add(schedule.vested_at(now).saturating_sub(schedule.claimed))
+				},
+			);
+			VestingBalance { locked: VestingLocks::<T>::get(currency_code, did), vested }
+		}
+
 		/// An account is being created.
 		pub fn on_created_account(did: Did) {
 			let who = T::DidResolution::get_account_id(&did);
@@ -271,33 +1163,353 @@ pub mod pallet {
 			let senders_did = T::DidResolution::get_did(&senders_acccount_id);
 			let vc_struct = Self::get_vc_struct(vc_id, vc_type, vc_type_error)?;
 
-			// ensure sender has associated vc
-			ensure!(senders_did.eq(&Some(vc_struct.owner)), Error::<T>::DidNotRegisteredWithVC);
+			// ensure sender owns the vc, the owner changes when the vc is transferred
+			let vc_owner = T::VCResolution::get_vc_owner(&vc_id);
+			ensure!(vc_owner.is_some() && senders_did.eq(&vc_owner), Error::<T>::DidNotRegisteredWithVC);
 
 			Ok(vc_struct)
 		}
 
-		/// Get VC Owner
-		fn get_vc_owner<G: codec::Decode + HasVCId>(
-			vc_struct: VC<T::Hash>,
-		) -> Result<T::AccountId, DispatchError> {
-			let vc_property: G = T::VCResolution::decode_vc::<G>(&vc_struct.vc_property)?;
+		/// Get the owner of the TokenVC linked to the VC property
+		///
+		/// The currency code must be registered for a token created from the linked TokenVC. A
+		/// superseded TokenVC and its replacement link the same token.
+		fn get_vc_owner<G: HasVCId>(
+			vc_property: &G,
+			currency_code: &CurrencyCode,
+		) -> Result<Did, DispatchError> {
+			let token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			ensure!(
+				T::VCResolution::latest_vc_id(&token.vc_id) ==
+					T::VCResolution::latest_vc_id(&vc_property.vc_id()),
+				Error::<T>::CurrencyCodeMismatch
+			);
 
-			let token_vc_struct =
-				if let Some(vc_struct) = T::VCResolution::get_vc(&vc_property.vc_id()) {
-					vc_struct
+			// follow the current owner of the token vc
+			let token_vc_owner =
+				if let Some(owner) = T::VCResolution::get_vc_owner(&vc_property.vc_id()) {
+					owner
 				} else {
 					fail!(Error::<T>::LinkedVCNotFound);
 				};
 
-			let owners_acc_id = if let Some(owners_acc_id) =
-				T::DidResolution::get_account_id(&token_vc_struct.owner)
+			ensure!(
+				T::DidResolution::did_exists(MultiAddress::Did(token_vc_owner)),
+				Error::<T>::DIDDoesNotExist
+			);
+			Ok(token_vc_owner)
+		}
+
+		/// Ensure the origin is the governance or the current owner of the TokenVC of the token
+		fn ensure_token_admin(
+			origin: OriginFor<T>,
+			currency_code: &CurrencyCode,
+		) -> DispatchResult {
+			let token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			let origin = match T::GovernanceOrigin::try_origin(origin) {
+				Ok(_) => return Ok(()),
+				Err(origin) => origin,
+			};
+			let sender = ensure_signed(origin)?;
+			let sender_did = T::DidResolution::get_did(&sender);
+			ensure!(
+				sender_did.is_some() && sender_did == T::VCResolution::get_vc_owner(&token.vc_id),
+				Error::<T>::NotTokenOwner
+			);
+			Ok(())
+		}
+
+		/// Credit the Did with newly issued tokens, within the maximum supply of the token
+		pub(crate) fn do_mint(
+			currency_code: CurrencyCode,
+			did: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let mut token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			let issuance = TotalIssuance::<T>::get(currency_code)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+			if let Some(max_supply) = token.max_supply {
+				ensure!(issuance <= max_supply, Error::<T>::MaxSupplyExceeded);
+			}
+			let balance = TokenBalances::<T>::get(currency_code, did)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+
+			token.total_minted = token.total_minted.saturating_add(amount);
+			Tokens::<T>::insert(currency_code, token);
+			Self::set_balance(currency_code, did, balance);
+			TotalIssuance::<T>::insert(currency_code, issuance);
+			Ok(())
+		}
+
+		/// Remove tokens held by the Did from the issuance
+		pub(crate) fn do_burn(
+			currency_code: CurrencyCode,
+			did: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let mut token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			let balance = TokenBalances::<T>::get(currency_code, did)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+			Self::ensure_unlocked(currency_code, did, balance)?;
+
+			token.total_burned = token.total_burned.saturating_add(amount);
+			Tokens::<T>::insert(currency_code, token);
+			Self::set_balance(currency_code, did, balance);
+			TotalIssuance::<T>::mutate(currency_code, |issuance| {
+				*issuance = issuance.saturating_sub(amount)
+			});
+			Ok(())
+		}
+
+		/// Move tokens between Dids, frozen holdings and locked tokens can not be moved
+		pub(crate) fn do_transfer(
+			currency_code: CurrencyCode,
+			from: Did,
+			to: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure!(Tokens::<T>::contains_key(currency_code), Error::<T>::TokenNotRegistered);
+			ensure!(
+				!FrozenHoldings::<T>::contains_key(currency_code, from),
+				Error::<T>::HoldingFrozen
+			);
+			let from_balance = TokenBalances::<T>::get(currency_code, from)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+			Self::ensure_unlocked(currency_code, from, from_balance)?;
+			if from == to {
+				return Ok(())
+			}
+			let to_balance = TokenBalances::<T>::get(currency_code, to)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+
+			Self::set_balance(currency_code, from, from_balance);
+			Self::set_balance(currency_code, to, to_balance);
+			Ok(())
+		}
+
+		/// Move tokens of the Did to its held balance, tokens can not leave a frozen holding
+		pub(crate) fn do_hold(
+			currency_code: CurrencyCode,
+			did: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure!(Tokens::<T>::contains_key(currency_code), Error::<T>::TokenNotRegistered);
+			ensure!(
+				!FrozenHoldings::<T>::contains_key(currency_code, did),
+				Error::<T>::HoldingFrozen
+			);
+			let balance = TokenBalances::<T>::get(currency_code, did)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+			Self::ensure_unlocked(currency_code, did, balance)?;
+			let held = HeldBalances::<T>::get(currency_code, did)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+
+			Self::set_held(currency_code, did, held);
+			Self::set_balance(currency_code, did, balance);
+			Ok(())
+		}
+
+		/// Return held tokens to the balance of the Did
+		pub(crate) fn do_release(
+			currency_code: CurrencyCode,
+			did: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let held = HeldBalances::<T>::get(currency_code, did)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+			let balance = TokenBalances::<T>::get(currency_code, did)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+
+			Self::set_held(currency_code, did, held);
+			Self::set_balance(currency_code, did, balance);
+			Ok(())
+		}
+
+		/// Remove held tokens of the Did from the issuance
+		pub(crate) fn do_burn_held(
+			currency_code: CurrencyCode,
+			did: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let mut token = Tokens::<T>::get(currency_code).ok_or(Error::<T>::TokenNotRegistered)?;
+			let held = HeldBalances::<T>::get(currency_code, did)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+
+			token.total_burned = token.total_burned.saturating_add(amount);
+			Tokens::<T>::insert(currency_code, token);
+			Self::set_held(currency_code, did, held);
+			TotalIssuance::<T>::mutate(currency_code, |issuance| {
+				*issuance = issuance.saturating_sub(amount)
+			});
+			Ok(())
+		}
+
+		/// Did of the signed origin granting an allowance on a registered token to the spender
+		fn allowance_owner(
+			origin: OriginFor<T>,
+			currency_code: &CurrencyCode,
+			spender: &Did,
+		) -> Result<Did, DispatchError> {
+			let sender = ensure_signed(origin)?;
+			let owner = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+			ensure!(Tokens::<T>::contains_key(currency_code), Error::<T>::TokenNotRegistered);
+			ensure!(
+				T::DidResolution::did_exists(MultiAddress::Did(*spender)),
+				Error::<T>::DIDDoesNotExist
+			);
+			Ok(owner)
+		}
+
+		/// Set the allowance of the spender, empty allowances are removed
+		fn set_allowance(
+			currency_code: CurrencyCode,
+			owner: Did,
+			spender: Did,
+			amount: BalanceOf<T>,
+		) {
+			if amount.is_zero() {
+				Allowances::<T>::remove((owner, spender, currency_code));
+			} else {
+				Allowances::<T>::insert((owner, spender, currency_code), amount);
+			}
+			Self::deposit_event(Event::AllowanceSet { currency_code, owner, spender, amount });
+		}
+
+		/// Did of the signed origin and the open escrow with the id
+		fn escrow_party(
+			origin: OriginFor<T>,
+			id: EscrowId,
+		) -> Result<(Did, EscrowOf<T>), DispatchError> {
+			let sender = ensure_signed(origin)?;
+			let did = T::DidResolution::get_did(&sender).ok_or(Error::<T>::DIDDoesNotExist)?;
+			let escrow = Escrows::<T>::get(id).ok_or(Error::<T>::EscrowNotFound)?;
+			Ok((did, escrow))
+		}
+
+		/// Pay the escrowed tokens to the recipient, or refund them to the sender, and close the
+		/// escrow
+		fn settle_escrow(id: EscrowId, escrow: EscrowOf<T>, release: bool) -> DispatchResult {
+			let Escrow { currency_code, sender, recipient, amount, .. } = escrow;
+			if release {
+				Self::do_transfer_held(currency_code, sender, recipient, amount)?;
+				Self::deposit_event(Event::EscrowReleased { id, currency_code, recipient, amount });
+			} else {
+				Self::do_release(currency_code, sender, amount)?;
+				Self::deposit_event(Event::EscrowRefunded { id, currency_code, sender, amount });
+			}
+
+			Escrows::<T>::remove(id);
+			EscrowsOf::<T>::mutate_exists(sender, |ids| {
+				if let Some(open) = ids {
+					open.retain(|open_id| *open_id != id);
+					if open.is_empty() {
+						*ids = None;
+					}
+				}
+			});
+			Ok(())
+		}
+
+		/// Remove the escrow from the escrows refunded at the deadline
+		fn remove_expiry(deadline: T::BlockNumber, id: EscrowId) {
+			EscrowExpiries::<T>::mutate_exists(deadline, |ids| {
+				if let Some(expiring) = ids {
+					expiring.retain(|expiring_id| *expiring_id != id);
+					if expiring.is_empty() {
+						*ids = None;
+					}
+				}
+			});
+		}
+
+		/// Move held tokens of a Did to the balance of another Did, tokens can neither leave nor
+		/// reach a frozen holding
+		pub(crate) fn do_transfer_held(
+			currency_code: CurrencyCode,
+			from: Did,
+			to: Did,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			for did in [from, to] {
+				ensure!(
+					!FrozenHoldings::<T>::contains_key(currency_code, did),
+					Error::<T>::HoldingFrozen
+				);
+			}
+			let held = HeldBalances::<T>::get(currency_code, from)
+				.checked_sub(&amount)
+				.ok_or(Error::<T>::BalanceTooLow)?;
+			let balance = TokenBalances::<T>::get(currency_code, to)
+				.checked_add(&amount)
+				.ok_or(Error::<T>::BalanceOverflow)?;
+
+			Self::set_held(currency_code, from, held);
+			Self::set_balance(currency_code, to, balance);
+			Ok(())
+		}
+
+		/// Ensure the balance left to the Did covers its tokens locked by vesting
+		fn ensure_unlocked(
+			currency_code: CurrencyCode,
+			did: Did,
+			balance: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure!(
+				balance >= VestingLocks::<T>::get(currency_code, did),
+				Error::<T>::BalanceLocked
+			);
+			Ok(())
+		}
+
+		/// The pending redemption request with the id
+		fn pending_redemption(id: RedemptionId) -> Result<Redemption<BalanceOf<T>>, DispatchError> {
+			Redemptions::<T>::get(id).ok_or_else(|| Error::<T>::RedemptionNotFound.into())
+		}
+
+		/// Set the held balance of the Did, empty balances are removed
+		///
+		/// Every change of a held balance goes through here to keep the tokens of the Dids
+		/// indexed.
+		fn set_held(currency_code: CurrencyCode, did: Did, held: BalanceOf<T>) {
+			if held.is_zero() {
+				HeldBalances::<T>::remove(currency_code, did);
+			} else {
+				HeldBalances::<T>::insert(currency_code, did, held);
+			}
+			Self::index_holder(currency_code, did);
+		}
+
+		/// Set the balance of the Did, empty balances are removed
+		///
+		/// Every change of a balance goes through here to keep the tokens of the Dids indexed.
+		fn set_balance(currency_code: CurrencyCode, did: Did, balance: BalanceOf<T>) {
+			if balance.is_zero() {
+				TokenBalances::<T>::remove(currency_code, did);
+			} else {
+				TokenBalances::<T>::insert(currency_code, did, balance);
+			}
+			Self::index_holder(currency_code, did);
+		}
+
+		/// Index the token for the Did while it has a free or held balance of it
+		fn index_holder(currency_code: CurrencyCode, did: Did) {
+			if TokenBalances::<T>::contains_key(currency_code, did) ||
+				HeldBalances::<T>::contains_key(currency_code, did)
 			{
-				owners_acc_id
+				HolderTokens::<T>::insert(did, currency_code, ());
 			} else {
-				fail!(Error::<T>::DIDDoesNotExist);
-			};
-			Ok(owners_acc_id)
+				HolderTokens::<T>::remove(did, currency_code);
+			}
 		}
 
 		// Get vc struct
@@ -320,7 +1532,7 @@ pub mod pallet {
 			ensure!(vc_struct.vc_type.eq(&vc_type), vc_type_error);
 
 			// ensure VC is unused
-			ensure!(!vc_struct.is_vc_used, Error::<T>::VCAlreadyUsed);
+			ensure!(!vc_struct.is_vc_used(), Error::<T>::VCAlreadyUsed);
 
 			Ok(vc_struct)
 		}
@@ -0,0 +1,2 @@
+/// Registration of the tokens described by the TokenVCs stored before per-currency balances
+pub mod v1;
@@ -0,0 +1,80 @@
+use crate::*;
+use frame_support::{
+	sp_runtime::traits::{Bounded, Zero},
+	traits::{Get, GetStorageVersion, NamedReservableCurrency, ReservableCurrency},
+	weights::Weight,
+};
+
+/// Register a token for each currency code of the stored TokenVCs
+///
+/// Before per-currency balances a TokenVC described a token of the native currency, and the
+/// balance reserved for it was a plain reserve of its owner. The first TokenVC found for a
+/// currency code registers the token, and up to its reservable balance of the owner's reserve
+/// is moved under the currency code so `withdraw_reserved` can reach it. The tokens minted
+/// before were native balance which stays with its holders, so the issuance of a registered
+/// token starts at zero.
+pub fn migrate<T: Config>() -> Weight {
+	let mut read: u64 = 0;
+	let mut written: u64 = 0;
+	for (vc_id, vc) in T::VCResolution::vcs_of_type(VCType::TokenVC) {
+		read += 1;
+		let token_vc = match T::VCResolution::decode_vc::<TokenVC>(&vc.vc_property) {
+			Ok(token_vc) => token_vc,
+			Err(_) => continue,
+		};
+		let currency_code = token_vc.currency_code;
+		read += 3;
+		if Tokens::<T>::contains_key(currency_code) {
+			continue;
+		}
+		let issuer = match T::VCResolution::get_vc_owner(&vc_id) {
+			Some(issuer) => issuer,
+			None => continue,
+		};
+		let reserve_account = match T::DidResolution::get_account_id(&issuer) {
+			Some(account) => account,
+			None => continue,
+		};
+
+		read += 1;
+		let reservable: BalanceOf<T> = token_vc
+			.reservable_balance
+			.try_into()
+			.unwrap_or_else(|_| BalanceOf::<T>::max_value());
+		let reserved = reservable.min(T::Currency::reserved_balance(&reserve_account));
+		T::Currency::unreserve(&reserve_account, reserved);
+		let reserved = match T::Currency::reserve_named(&currency_code, &reserve_account, reserved)
+		{
+			Ok(()) => reserved,
+			Err(_) => {
+				// the balance can not be reserved again under the currency code, it stays a plain
+				// reserve
+				let _ = T::Currency::reserve(&reserve_account, reserved);
+				Zero::zero()
+			},
+		};
+		let max_supply = token_vc.max_supply.and_then(|max_supply| max_supply.try_into().ok());
+
+		Tokens::<T>::insert(
+			currency_code,
+			TokenInfo {
+				token_name: token_vc.token_name,
+				decimal: token_vc.decimal,
+				issuer,
+				vc_id,
+				reserve_account,
+				reserved,
+				total_minted: Zero::zero(),
+				total_burned: Zero::zero(),
+				max_supply,
+			},
+		);
+		TokenVCs::<T>::insert(vc_id, currency_code);
+		TotalIssuance::<T>::insert(currency_code, BalanceOf::<T>::zero());
+		written += 5;
+	}
+
+	Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+	T::DbWeight::get().reads_writes(read + 1, written + 1)
+}
@@ -1,7 +1,7 @@
 use crate as pallet_token;
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{ConstU32, Everything, GenesisBuild},
+	traits::{ConstU32, ConstU64, Everything, GenesisBuild},
 };
 use frame_system::{self as system, EnsureSignedBy};
 use pallet_balances;
@@ -15,7 +15,6 @@ use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
-pub type CurrencyCode = [u8; 8];
 use system::EnsureSigned;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -97,9 +96,13 @@ impl pallet_balances::Config for Test {
 impl pallet_token::Config for Test {
 	type Event = Event;
 	type WithdrawOrigin = EnsureSigned<Self::AccountId>;
+	type GovernanceOrigin = frame_system::EnsureRoot<u64>;
 	type Currency = Balances;
 	type DidResolution = Did;
 	type VCResolution = VC;
+	type MaxVestingSchedules = ConstU32<3>;
+	type MaxEscrows = ConstU32<2>;
+	type MaxEscrowExpiries = ConstU32<2>;
 }
 
 impl pallet_vc::Config for Test {
@@ -108,6 +111,18 @@ impl pallet_vc::Config for Test {
 	type IsCouncilMember = ();
 	type IsValidator = ();
 	type DidResolution = Did;
+	type TransferOrigin = frame_system::EnsureRoot<u64>;
+	type RetentionPeriod = ConstU64<10>;
+	type MaxPrunedPerBlock = ConstU32<2>;
+	type MaxRetiredPerBlock = ConstU32<3>;
+	type MaxSupersedeDepth = ConstU32<2>;
+	type MaxIssuers = ConstU32<10>;
+	type MaxSignaturesPerBatch = ConstU32<4>;
+	type Currency = Balances;
+	type VCDepositBase = ConstU64<0>;
+	type VCDepositPerByte = ConstU64<0>;
+	type VCLinks = Token;
+	type WeightInfo = ();
 }
 
 pub const ALICE: metamui_primitives::Did = *b"did:ssid:swn\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
@@ -139,12 +154,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		initial_dids: vec![
 			DIdentity::Private(PrivateDid {
 				identifier: BOB,
-				public_key: sr25519::Pair::from_seed(&BOB_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&BOB_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 			DIdentity::Private(PrivateDid {
 				identifier: DAVE,
-				public_key: sr25519::Pair::from_seed(&DAVE_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&DAVE_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 		],
@@ -1,11 +1,24 @@
 use super::*;
 use crate::{
-	mock::{Balances, Token, VC, *},
+	mock::{Balances, Event, Token, VC, *},
 };
-use frame_support::{assert_noop, assert_ok};
-use metamui_primitives::types::{SlashMintTokens, TokenVC, VC as VCStruct};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		tokens::{
+			fungibles::{Inspect, Mutate, Transfer},
+			DepositConsequence, WithdrawConsequence,
+		},
+		GetStorageVersion, Hooks, NamedReservableCurrency, ReservableCurrency, StorageVersion,
+	},
+	weights::Weight,
+};
+use metamui_primitives::types::{PublicKey, SlashMintTokens, TokenVC, VC as VCStruct};
 use sp_core::{sr25519, Pair, H256};
-use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_runtime::{
+	traits::{BadOrigin, BlakeTwo256, Hash},
+	MultiSignature,
+};
 
 #[test]
 fn test_mint_token() {
@@ -16,6 +29,7 @@ fn test_mint_token() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -24,14 +38,15 @@ fn test_mint_token() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -42,7 +57,8 @@ fn test_mint_token() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let mint_amount: u128 = 1_000_000;
 		let mint_vc = SlashMintTokens { vc_id, currency_code, amount: mint_amount };
@@ -53,14 +69,15 @@ fn test_mint_token() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: mint_vc,
@@ -69,11 +86,11 @@ fn test_mint_token() {
 		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
 		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
 
-		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64));
 
 		// checking correctness of free balance after mint
-		assert_eq!(Balances::free_balance(&BOB_ACCOUNT_ID), (token_amount + mint_amount) as u64);
-		assert_eq!(Balances::total_issuance(), (token_amount + mint_amount) as u64);
+		assert_eq!(Token::token_balance(currency_code, BOB), (token_amount + mint_amount) as u64);
+		assert_eq!(Token::total_issuance(currency_code), (token_amount + mint_amount) as u64);
 	});
 }
 
@@ -86,6 +103,7 @@ fn test_mint_token_fails() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -94,14 +112,15 @@ fn test_mint_token_fails() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -112,7 +131,8 @@ fn test_mint_token_fails() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let mint_amount: u128 = 1_000_000;
 		let mint_vc = SlashMintTokens { vc_id, currency_code,  amount: mint_amount };
@@ -123,14 +143,15 @@ fn test_mint_token_fails() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: mint_vc,
@@ -139,15 +160,15 @@ fn test_mint_token_fails() {
 		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
 		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
 
-		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64));
 
 		// checking correctness of free balance after mint
-		assert_eq!(Balances::free_balance(&BOB_ACCOUNT_ID), (token_amount + mint_amount) as u64);
-		assert_eq!(Balances::total_issuance(), (token_amount + mint_amount) as u64);
+		assert_eq!(Token::token_balance(currency_code, BOB), (token_amount + mint_amount) as u64);
+		assert_eq!(Token::total_issuance(currency_code), (token_amount + mint_amount) as u64);
 
 		// checking mint token vc works after being used
 		assert_noop!(
-			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id),
+			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64),
 			Error::<Test>::VCAlreadyUsed
 		);
 	});
@@ -162,6 +183,7 @@ fn test_mint_token_fails_invalidvc() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -170,14 +192,15 @@ fn test_mint_token_fails_invalidvc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -188,7 +211,8 @@ fn test_mint_token_fails_invalidvc() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let mint_amount: u128 = 1_000_000;
 		let mint_vc = SlashMintTokens { vc_id, currency_code,  amount: mint_amount };
@@ -199,14 +223,15 @@ fn test_mint_token_fails_invalidvc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: mint_vc,
@@ -216,7 +241,7 @@ fn test_mint_token_fails_invalidvc() {
 		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
 
 		assert_noop!(
-			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id),
+			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64),
 			Error::<Test>::InvalidVC
 		);
 	});
@@ -231,6 +256,7 @@ fn test_slash_token() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -239,14 +265,15 @@ fn test_slash_token() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -257,7 +284,8 @@ fn test_slash_token() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let slash_amount: u128 = 1_000_000;
 		let slash_vc = SlashMintTokens { vc_id, currency_code,  amount: slash_amount };
@@ -268,14 +296,15 @@ fn test_slash_token() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &slash_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: slash_vc,
@@ -287,8 +316,8 @@ fn test_slash_token() {
 		assert_ok!(Token::slash_token(Origin::signed(BOB_ACCOUNT_ID), vc_id));
 
 		// checking correctness of free balance after mint
-		assert_eq!(Balances::free_balance(&BOB_ACCOUNT_ID), (token_amount - slash_amount) as u64);
-		assert_eq!(Balances::total_issuance(), (token_amount - slash_amount) as u64);
+		assert_eq!(Token::token_balance(currency_code, BOB), (token_amount - slash_amount) as u64);
+		assert_eq!(Token::total_issuance(currency_code), (token_amount - slash_amount) as u64);
 	});
 }
 
@@ -301,6 +330,7 @@ fn test_slash_token_fails() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -309,14 +339,15 @@ fn test_slash_token_fails() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -327,7 +358,8 @@ fn test_slash_token_fails() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let slash_amount: u128 = 1_000_000;
 		let slash_vc = SlashMintTokens { vc_id, currency_code,  amount: slash_amount };
@@ -338,14 +370,15 @@ fn test_slash_token_fails() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &slash_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: slash_vc,
@@ -357,8 +390,8 @@ fn test_slash_token_fails() {
 		assert_ok!(Token::slash_token(Origin::signed(BOB_ACCOUNT_ID), vc_id));
 
 		// checking correctness of free balance after mint
-		assert_eq!(Balances::free_balance(&BOB_ACCOUNT_ID), (token_amount - slash_amount) as u64);
-		assert_eq!(Balances::total_issuance(), (token_amount - slash_amount) as u64);
+		assert_eq!(Token::token_balance(currency_code, BOB), (token_amount - slash_amount) as u64);
+		assert_eq!(Token::total_issuance(currency_code), (token_amount - slash_amount) as u64);
 
 		// checking slash token vc works after being used
 		assert_noop!(
@@ -377,6 +410,7 @@ fn test_slash_token_fails_invalidvc() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -385,14 +419,15 @@ fn test_slash_token_fails_invalidvc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -403,7 +438,8 @@ fn test_slash_token_fails_invalidvc() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let slash_amount: u128 = 1_000_000;
 		let slash_vc = SlashMintTokens { vc_id, currency_code,  amount: slash_amount };
@@ -414,14 +450,15 @@ fn test_slash_token_fails_invalidvc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &slash_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: slash_vc,
@@ -446,6 +483,7 @@ fn test_slash_token_fails_lowbalance() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -454,14 +492,15 @@ fn test_slash_token_fails_lowbalance() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -472,7 +511,8 @@ fn test_slash_token_fails_lowbalance() {
 
 		let token_amount: u128 = 2_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let slash_amount: u128 = 3_000_000;
 		let slash_vc = SlashMintTokens { vc_id, currency_code,  amount: slash_amount };
@@ -483,14 +523,15 @@ fn test_slash_token_fails_lowbalance() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &slash_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: slash_vc,
@@ -509,6 +550,7 @@ fn test_slash_token_fails_lowbalance() {
 #[test]
 fn test_withdraw_reserve_works() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
 		let reservable_balance: u128 = 1_000_000;
 		let token_amount: u128 = 5_000_000;
 		let token_vc = TokenVC {
@@ -516,6 +558,7 @@ fn test_withdraw_reserve_works() {
 			reservable_balance,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -524,14 +567,15 @@ fn test_withdraw_reserve_works() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -539,9 +583,25 @@ fn test_withdraw_reserve_works() {
 
 		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
 
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+
 		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		assert_ok!(Token::register_token(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+		assert_eq!(Balances::reserved_balance_named(&currency_code, &BOB_ACCOUNT_ID), 1_000_000);
 
-		assert_ok!(Token::withdraw_reserved(Origin::signed(BOB_ACCOUNT_ID), DAVE, BOB, 1_000_000));
+		assert_ok!(Token::withdraw_reserved(
+			Origin::signed(BOB_ACCOUNT_ID),
+			DAVE,
+			currency_code,
+			600_000
+		));
+		System::assert_last_event(Event::Token(crate::Event::ReserveWithdrawn {
+			currency_code,
+			from: BOB,
+			to: DAVE,
+			amount: 600_000,
+		}));
 
 		// check balance has been credited correctly
 		assert_eq!(
@@ -550,13 +610,33 @@ fn test_withdraw_reserve_works() {
 		);
 		assert_eq!(
 			Balances::reserved_balance(BOB_ACCOUNT_ID),
-			(reservable_balance - 1_000_000) as u64
+			(reservable_balance - 600_000) as u64
+		);
+		assert_eq!(Balances::total_balance(&BOB_ACCOUNT_ID), (token_amount - 600_000) as u64);
+		assert_eq!(Balances::free_balance(DAVE_ACCOUNT_ID), (INITIAL_BALANCE + 600_000) as u64);
+		assert_eq!(Token::token_info(currency_code).unwrap().reserved, 400_000);
+
+		// withdrawals are limited to what is left of the reserve of the token
+		assert_noop!(
+			Token::withdraw_reserved(
+				Origin::signed(BOB_ACCOUNT_ID),
+				DAVE,
+				currency_code,
+				400_001
+			),
+			Error::<Test>::ReserveTooLow
 		);
-		assert_eq!(Balances::total_balance(&BOB_ACCOUNT_ID), (token_amount - 1_000_000) as u64);
-		assert_eq!(Balances::free_balance(DAVE_ACCOUNT_ID), (INITIAL_BALANCE + 1_000_000) as u64);
 
-		// check created token details
-		assert_eq!(Balances::total_issuance(), 5000000);
+		// the reserve stays on the account it was made from when the issuer rotates its key
+		let public_key = PublicKey::Sr25519(sr25519::Pair::from_seed(&[9; 32]).public());
+		assert_ok!(pallet_did::Pallet::<Test>::do_rotate_key(&BOB, &public_key));
+		assert_ok!(Token::withdraw_reserved(
+			Origin::signed(DAVE_ACCOUNT_ID),
+			DAVE,
+			currency_code,
+			400_000
+		));
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), 0);
 	});
 }
 
@@ -570,6 +650,7 @@ fn test_withdraw_reserve_fails() {
 			reservable_balance,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -578,14 +659,15 @@ fn test_withdraw_reserve_fails() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -595,10 +677,39 @@ fn test_withdraw_reserve_fails() {
 
 		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
 
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+
 		assert_noop!(
-			Token::withdraw_reserved(Origin::signed(BOB_ACCOUNT_ID), ALICE, BOB, 1_000_000),
+			Token::withdraw_reserved(
+				Origin::signed(BOB_ACCOUNT_ID),
+				ALICE,
+				currency_code,
+				1_000_000
+			),
 			Error::<Test>::DIDDoesNotExist
 		);
+		assert_noop!(
+			Token::withdraw_reserved(
+				Origin::signed(BOB_ACCOUNT_ID),
+				DAVE,
+				currency_code,
+				1_000_000
+			),
+			Error::<Test>::TokenNotRegistered
+		);
+
+		// at most the reserve of the token can be withdrawn
+		assert_ok!(Token::register_token(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+		assert_noop!(
+			Token::withdraw_reserved(
+				Origin::signed(BOB_ACCOUNT_ID),
+				BOB,
+				currency_code,
+				1_000_001
+			),
+			Error::<Test>::ReserveTooLow
+		);
 	});
 }
 
@@ -611,6 +722,7 @@ fn test_transfer_token_works() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -619,14 +731,15 @@ fn test_transfer_token_works() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -637,7 +750,8 @@ fn test_transfer_token_works() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let transfer_amount: u128 = 1_000_000;
 		let token_transfer_vc = TokenTransferVC { vc_id, currency_code,  amount: transfer_amount };
@@ -648,14 +762,15 @@ fn test_transfer_token_works() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_transfer_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_transfer_vc,
@@ -664,12 +779,20 @@ fn test_transfer_token_works() {
 		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
 		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
 
-		assert_ok!(Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE));
+		assert_ok!(Token::transfer_token(
+			Origin::signed(BOB_ACCOUNT_ID),
+			vc_id,
+			DAVE,
+			transfer_amount as u64
+		));
 
 		// check balance transfer worked correctly
-		assert_eq!(Balances::free_balance(BOB_ACCOUNT_ID), (token_amount - transfer_amount) as u64);
-		assert_eq!(Balances::free_balance(DAVE_ACCOUNT_ID), transfer_amount as u64);
-		assert_eq!(Balances::total_issuance(), token_amount as u64);
+		assert_eq!(
+			Token::token_balance(currency_code, BOB),
+			(token_amount - transfer_amount) as u64
+		);
+		assert_eq!(Token::token_balance(currency_code, DAVE), transfer_amount as u64);
+		assert_eq!(Token::total_issuance(currency_code), token_amount as u64);
 	});
 }
 
@@ -682,6 +805,7 @@ fn test_transfer_token_fails_lowbalance() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -690,14 +814,15 @@ fn test_transfer_token_fails_lowbalance() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -708,7 +833,8 @@ fn test_transfer_token_fails_lowbalance() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let transfer_amount: u128 = 7_000_000;
 		let token_transfer_vc = TokenTransferVC { vc_id, currency_code,  amount: transfer_amount };
@@ -719,14 +845,15 @@ fn test_transfer_token_fails_lowbalance() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_transfer_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_transfer_vc,
@@ -736,8 +863,8 @@ fn test_transfer_token_fails_lowbalance() {
 		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
 
 		assert_noop!(
-			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE),
-			pallet_balances::Error::<Test>::InsufficientBalance
+			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE, transfer_amount as u64),
+			Error::<Test>::BalanceTooLow
 		);
 	});
 }
@@ -751,6 +878,7 @@ fn test_transfer_token_fails_invalidvc() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -759,14 +887,15 @@ fn test_transfer_token_fails_invalidvc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -777,7 +906,8 @@ fn test_transfer_token_fails_invalidvc() {
 
 		let token_amount: u128 = 5_000_000;
 
-		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, token_amount.try_into().unwrap());
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
 
 		let transfer_amount: u128 = 1_000_000;
 		let token_transfer_vc = TokenTransferVC { vc_id, currency_code,  amount: transfer_amount };
@@ -788,14 +918,15 @@ fn test_transfer_token_fails_invalidvc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_transfer_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc_struct: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_transfer_vc,
@@ -805,8 +936,1200 @@ fn test_transfer_token_fails_invalidvc() {
 		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
 
 		assert_noop!(
-			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE),
+			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE, transfer_amount as u64),
 			Error::<Test>::InvalidVC
 		);
 	});
 }
+
+#[test]
+fn test_mint_token_multi_use_vc() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code,
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: token_vc,
+		};
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
+
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+		register_token(vc_id);
+
+		let mint_amount: u128 = 1_000_000;
+		let mint_vc = SlashMintTokens { vc_id, currency_code, amount: mint_amount };
+
+		let mint_vc: [u8; 128] = convert_to_array::<128>(mint_vc.encode());
+		let vc_type = VCType::MintTokens;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let max_uses = Some(2);
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers, &max_uses));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses,
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: mint_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+
+		// the same vc can be used twice
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64));
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64));
+
+		assert_eq!(Token::token_balance(currency_code, BOB), (2 * mint_amount) as u64);
+		assert_eq!(Token::total_issuance(currency_code), (2 * mint_amount) as u64);
+
+		assert_noop!(
+			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, mint_amount as u64),
+			Error::<Test>::VCAlreadyUsed
+		);
+	});
+}
+
+#[test]
+fn test_transfer_token_partial_amount() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code,
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: token_vc,
+		};
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
+
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+
+		let token_amount: u128 = 5_000_000;
+
+		register_token(vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, token_amount as u64);
+
+		// vc authorises a ceiling which is drawn down in several transfers
+		let transfer_amount: u128 = 1_000_000;
+		let token_transfer_vc = TokenTransferVC { vc_id, currency_code, amount: transfer_amount };
+
+		let token_transfer_vc: [u8; 128] = convert_to_array::<128>(token_transfer_vc.encode());
+		let vc_type = VCType::TokenTransferVC;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_transfer_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: token_transfer_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+
+		assert_ok!(Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE, 400_000));
+		assert_eq!(pallet_vc::Pallet::<Test>::remaining_amount(vc_id), Ok(600_000));
+
+		// can not draw more than what is left in the vc
+		assert_noop!(
+			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE, 600_001),
+			pallet_vc::Error::<Test>::AmountExceedsRemaining
+		);
+
+		assert_ok!(Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE, 600_000));
+
+		assert_eq!(
+			Token::token_balance(currency_code, BOB),
+			(token_amount - transfer_amount) as u64
+		);
+		assert_eq!(Token::token_balance(currency_code, DAVE), transfer_amount as u64);
+
+		// vc is used once the amount is exhausted
+		assert_noop!(
+			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE, 1),
+			Error::<Test>::VCAlreadyUsed
+		);
+	});
+}
+
+#[test]
+fn test_mint_token_after_token_vc_transfer() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code,
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: token_vc,
+		};
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()));
+
+		let token_vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+		register_token(token_vc_id);
+
+		// DAVE becomes the administrator of the token
+		assert_ok!(VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), token_vc_id, DAVE));
+		assert_ok!(VC::force_approve_transfer(Origin::root(), token_vc_id));
+
+		let mint_amount: u128 = 1_000_000;
+		let mint_vc = SlashMintTokens { vc_id: token_vc_id, currency_code, amount: mint_amount };
+
+		let mint_vc: [u8; 128] = convert_to_array::<128>(mint_vc.encode());
+		let vc_type = VCType::MintTokens;
+
+		// the previous owner can no longer authorise minting
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type: vc_type.clone(),
+			vc_property: mint_vc,
+		};
+
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), vc_struct.encode()),
+			pallet_vc::Error::<Test>::VCNotSignedByTokenVCOwner
+		);
+
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&DAVE_SEED);
+		let owner = DAVE;
+		let issuers = vec![DAVE];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc_struct: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: mint_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(DAVE_ACCOUNT_ID), vc_struct.encode()));
+		let vc_id = *BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes();
+
+		assert_ok!(Token::mint_token(Origin::signed(DAVE_ACCOUNT_ID), vc_id, mint_amount as u64));
+
+		// the minted amount is credited to the new owner of the token vc
+		assert_eq!(Token::token_balance(currency_code, DAVE), mint_amount as u64);
+		assert_eq!(Token::token_balance(currency_code, BOB), 0);
+	});
+}
+
+/// Store a VC owned and signed by the given Did, returns its id
+fn store_vc<P: Encode>(
+	vc_type: VCType,
+	property: P,
+	owner: metamui_primitives::Did,
+	seed: &[u8; 32],
+	account: u64,
+) -> VCid {
+	let vc_struct = signed_vc(vc_type, property, owner, seed);
+	assert_ok!(VC::store(Origin::signed(account), vc_struct.encode()));
+	*BlakeTwo256::hash_of(&vc_struct).as_fixed_bytes()
+}
+
+/// A VC owned and signed by the given Did
+fn signed_vc<P: Encode>(
+	vc_type: VCType,
+	property: P,
+	owner: metamui_primitives::Did,
+	seed: &[u8; 32],
+) -> VCStruct<H256> {
+	let vc_property: [u8; 128] = convert_to_array::<128>(property.encode());
+	let pair: sr25519::Pair = sr25519::Pair::from_seed(seed);
+	let issuers = vec![owner];
+	let hash = BlakeTwo256::hash_of(&(&vc_type, &vc_property, &owner, &issuers));
+	let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+	VCStruct {
+		hash,
+		owner,
+		issuers,
+		signatures: vec![signature],
+		max_uses: Some(1),
+		used_count: 0,
+		is_vc_active: false,
+		vc_type,
+		vc_property,
+	}
+}
+
+/// Register the token of a TokenVC owned by BOB, with the balance it reserves
+fn register_token(token_vc_id: VCid) {
+	let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, 1_000);
+	assert_ok!(Token::register_token(Origin::signed(BOB_ACCOUNT_ID), token_vc_id));
+}
+
+/// Credit the account with tokens without a VC
+fn mint_tokens(currency_code: CurrencyCode, account: u64, amount: u64) {
+	assert_ok!(<Token as Mutate<u64>>::mint_into(currency_code, &account, amount));
+}
+
+/// Store a TokenVC owned by BOB
+fn store_token_vc(currency_code: &str, reservable_balance: u128) -> VCid {
+	store_capped_token_vc(currency_code, reservable_balance, None)
+}
+
+/// Store a TokenVC owned by BOB, with the maximum supply of the token
+fn store_capped_token_vc(
+	currency_code: &str,
+	reservable_balance: u128,
+	max_supply: Option<u128>,
+) -> VCid {
+	let token_vc = TokenVC {
+		token_name: convert_to_array::<16>("test".into()),
+		reservable_balance,
+		decimal: 6,
+		currency_code: convert_to_array::<8>(currency_code.into()),
+		max_supply,
+	};
+	store_vc(VCType::TokenVC, token_vc, BOB, &BOB_SEED, BOB_ACCOUNT_ID)
+}
+
+/// Store a MintTokens VC for the token, owned by BOB
+fn store_mint_vc(token_vc_id: VCid, currency_code: &str, amount: u128) -> VCid {
+	let mint_vc = SlashMintTokens {
+		vc_id: token_vc_id,
+		currency_code: convert_to_array::<8>(currency_code.into()),
+		amount,
+	};
+	store_vc(VCType::MintTokens, mint_vc, BOB, &BOB_SEED, BOB_ACCOUNT_ID)
+}
+
+#[test]
+fn test_register_token() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, 5_000);
+		let token_vc_id = store_token_vc("OTH", 1_000);
+
+		// only the owner of the TokenVC can register the token
+		assert_noop!(
+			Token::register_token(Origin::signed(DAVE_ACCOUNT_ID), token_vc_id),
+			Error::<Test>::DidNotRegisteredWithVC
+		);
+
+		assert_ok!(Token::register_token(Origin::signed(BOB_ACCOUNT_ID), token_vc_id));
+		assert_eq!(Balances::reserved_balance(&BOB_ACCOUNT_ID), 1_000);
+		assert_eq!(
+			Token::token_info(currency_code),
+			Some(TokenInfo {
+				token_name: convert_to_array::<16>("test".into()),
+				decimal: 6,
+				issuer: BOB,
+				vc_id: token_vc_id,
+				reserve_account: BOB_ACCOUNT_ID,
+				reserved: 1_000,
+				total_minted: 0,
+				total_burned: 0,
+				max_supply: None,
+			})
+		);
+
+		// the TokenVC is consumed
+		assert!(VC::is_vc_used(&token_vc_id));
+		assert_noop!(
+			Token::register_token(Origin::signed(BOB_ACCOUNT_ID), token_vc_id),
+			Error::<Test>::VCAlreadyUsed
+		);
+
+		// the currency code is taken
+		let other_vc_id = store_token_vc("OTH", 2_000);
+		assert_noop!(
+			Token::register_token(Origin::signed(BOB_ACCOUNT_ID), other_vc_id),
+			Error::<Test>::TokenAlreadyRegistered
+		);
+
+		let mint_vc_id = store_mint_vc(token_vc_id, "OTH", 300);
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 200));
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 100));
+		assert_eq!(Token::token_info(currency_code).unwrap().total_minted, 300);
+	});
+}
+
+#[test]
+fn test_superseded_token_vc() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, 2_000);
+		let token_vc =
+			|token_name: &str, reservable_balance: u128, decimal: u8, code: &str| TokenVC {
+				token_name: convert_to_array::<16>(token_name.into()),
+				reservable_balance,
+				decimal,
+				currency_code: convert_to_array::<8>(code.into()),
+				max_supply: Some(500),
+			};
+
+		// a new version can not move the token to another currency code or other decimals
+		let other_code =
+			signed_vc(VCType::TokenVC, token_vc("test", 1_000, 6, "NEW"), BOB, &BOB_SEED);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), token_vc_id, other_code.encode()),
+			Error::<Test>::CurrencyCodeMismatch
+		);
+		let other_decimal =
+			signed_vc(VCType::TokenVC, token_vc("test", 1_000, 8, "OTH"), BOB, &BOB_SEED);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), token_vc_id, other_decimal.encode()),
+			Error::<Test>::DecimalMismatch
+		);
+
+		let new_vc =
+			signed_vc(VCType::TokenVC, token_vc("renamed", 2_000, 6, "OTH"), BOB, &BOB_SEED);
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), token_vc_id, new_vc.encode()));
+		let new_vc_id = *BlakeTwo256::hash_of(&new_vc).as_fixed_bytes();
+		System::assert_has_event(Event::Token(crate::Event::TokenUpdated {
+			currency_code,
+			vc_id: new_vc_id,
+		}));
+
+		// the token follows the new version, the difference of the reservable balance is reserved
+		let token = Token::token_info(currency_code).unwrap();
+		assert_eq!((token.vc_id, token.reserved, token.max_supply), (new_vc_id, 2_000, None));
+		assert_eq!(token.token_name, convert_to_array::<16>("renamed".into()));
+		assert_eq!(Balances::reserved_balance_named(&currency_code, &BOB_ACCOUNT_ID), 2_000);
+		assert_eq!(Token::token_vc(token_vc_id), None);
+		assert_eq!(Token::token_vc(new_vc_id), Some(currency_code));
+
+		// VCs linked to either version of the TokenVC are for the same token
+		let mint_vc_id = store_mint_vc(new_vc_id, "OTH", 300);
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 300));
+		let mint_vc_id = store_mint_vc(token_vc_id, "OTH", 1_000);
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 1_000));
+		assert_eq!(Token::token_balance(currency_code, BOB), 1_300);
+
+		// a lower reservable balance releases the difference, within what is left of the reserve
+		assert_ok!(Token::withdraw_reserved(
+			Origin::signed(BOB_ACCOUNT_ID),
+			DAVE,
+			currency_code,
+			1_800
+		));
+		let shrunk_vc =
+			signed_vc(VCType::TokenVC, token_vc("shrunk", 500, 6, "OTH"), BOB, &BOB_SEED);
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), new_vc_id, shrunk_vc.encode()));
+		assert_eq!(Token::token_info(currency_code).unwrap().reserved, 0);
+		assert_eq!(Balances::reserved_balance_named(&currency_code, &BOB_ACCOUNT_ID), 0);
+	});
+}
+
+#[test]
+fn test_register_token_fails_lowbalance() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+
+		assert!(Token::register_token(Origin::signed(BOB_ACCOUNT_ID), token_vc_id).is_err());
+		assert_eq!(Token::token_info(currency_code), None);
+	});
+}
+
+#[test]
+fn test_token_balances_per_currency() {
+	new_test_ext().execute_with(|| {
+		let oth: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let xyz: CurrencyCode = convert_to_array::<8>("XYZ".into());
+		let oth_vc_id = store_token_vc("OTH", 1_000);
+
+		// tokens can only be minted once registered
+		let mint_vc_id = store_mint_vc(oth_vc_id, "OTH", 300);
+		assert_noop!(
+			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 300),
+			Error::<Test>::TokenNotRegistered
+		);
+		register_token(oth_vc_id);
+		let xyz_vc_id = store_token_vc("XYZ", 1_000);
+		register_token(xyz_vc_id);
+
+		// the VC must reference the TokenVC of its currency code
+		let mint_vc_id = store_mint_vc(oth_vc_id, "XYZ", 300);
+		assert_noop!(
+			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 300),
+			Error::<Test>::CurrencyCodeMismatch
+		);
+
+		let mint_vc_id = store_mint_vc(xyz_vc_id, "XYZ", 300);
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 300));
+		assert_eq!(Token::token_balance(xyz, BOB), 300);
+		assert_eq!(Token::token_balance(oth, BOB), 0);
+
+		// the fungibles traits see the same balances
+		assert_eq!(<Token as Inspect<u64>>::balance(xyz, &BOB_ACCOUNT_ID), 300);
+		assert_eq!(<Token as Inspect<u64>>::total_issuance(oth), 0);
+		assert_ok!(<Token as Transfer<u64>>::transfer(
+			xyz,
+			&BOB_ACCOUNT_ID,
+			&DAVE_ACCOUNT_ID,
+			100,
+			true
+		));
+		assert_eq!(Token::token_balance(xyz, DAVE), 100);
+		assert_eq!(
+			<Token as Mutate<u64>>::burn_from(xyz, &DAVE_ACCOUNT_ID, 101),
+			Err(Error::<Test>::BalanceTooLow.into())
+		);
+		assert_ok!(<Token as Mutate<u64>>::burn_from(xyz, &DAVE_ACCOUNT_ID, 100));
+		assert_eq!(Token::total_issuance(xyz), 200);
+		assert_noop!(
+			<Token as Mutate<u64>>::mint_into(*b"NONE\0\0\0\0", &BOB_ACCOUNT_ID, 1),
+			Error::<Test>::TokenNotRegistered
+		);
+	});
+}
+
+#[test]
+fn test_max_supply() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_capped_token_vc("OTH", 1_000, Some(1_000));
+		register_token(token_vc_id);
+		assert_eq!(Token::token_info(currency_code).unwrap().max_supply, Some(1_000));
+
+		let mint_vc_id = store_mint_vc(token_vc_id, "OTH", 2_000);
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 800));
+		assert_noop!(
+			Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 201),
+			Error::<Test>::MaxSupplyExceeded
+		);
+		assert_eq!(
+			<Token as Inspect<u64>>::can_deposit(currency_code, &BOB_ACCOUNT_ID, 201, true),
+			DepositConsequence::Overflow
+		);
+
+		// burning makes room for new tokens, transfers leave the totals unchanged
+		let slash_vc_id = store_vc(
+			VCType::SlashTokens,
+			SlashMintTokens { vc_id: token_vc_id, currency_code, amount: 300 },
+			BOB,
+			&BOB_SEED,
+			BOB_ACCOUNT_ID,
+		);
+		assert_ok!(Token::slash_token(Origin::signed(BOB_ACCOUNT_ID), slash_vc_id));
+		assert_ok!(<Token as Transfer<u64>>::transfer(
+			currency_code,
+			&BOB_ACCOUNT_ID,
+			&DAVE_ACCOUNT_ID,
+			100,
+			true
+		));
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 500));
+
+		let token = Token::token_info(currency_code).unwrap();
+		assert_eq!((token.total_minted, token.total_burned), (1_300, 300));
+		assert_eq!(Token::total_issuance(currency_code), 1_000);
+
+		// governance can only raise the cap above the circulating supply
+		assert_noop!(
+			Token::set_max_supply(Origin::signed(BOB_ACCOUNT_ID), currency_code, None),
+			BadOrigin
+		);
+		assert_noop!(
+			Token::set_max_supply(Origin::root(), currency_code, Some(999)),
+			Error::<Test>::MaxSupplyBelowIssuance
+		);
+		assert_ok!(Token::set_max_supply(Origin::root(), currency_code, Some(1_200)));
+		assert_ok!(Token::mint_token(Origin::signed(BOB_ACCOUNT_ID), mint_vc_id, 200));
+		assert_ok!(Token::set_max_supply(Origin::root(), currency_code, None));
+		assert_eq!(Token::token_info(currency_code).unwrap().max_supply, None);
+	});
+}
+
+#[test]
+fn test_freeze_and_thaw() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, 1_000);
+
+		// only the token owner or the governance can freeze a holding
+		assert_noop!(
+			Token::freeze(Origin::signed(DAVE_ACCOUNT_ID), currency_code, BOB, 1),
+			Error::<Test>::NotTokenOwner
+		);
+		assert_ok!(Token::freeze(Origin::signed(BOB_ACCOUNT_ID), currency_code, BOB, 1));
+		assert_eq!(Token::frozen(currency_code, BOB), Some(1));
+		System::assert_last_event(Event::Token(crate::Event::Frozen {
+			currency_code,
+			did: BOB,
+			reason: 1,
+		}));
+		assert_noop!(
+			Token::freeze(Origin::root(), currency_code, BOB, 2),
+			Error::<Test>::AlreadyFrozen
+		);
+
+		// tokens can not leave the frozen holding
+		let transfer_vc_id = store_vc(
+			VCType::TokenTransferVC,
+			TokenTransferVC { vc_id: token_vc_id, currency_code, amount: 500 },
+			BOB,
+			&BOB_SEED,
+			BOB_ACCOUNT_ID,
+		);
+		assert_noop!(
+			Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), transfer_vc_id, DAVE, 500),
+			Error::<Test>::HoldingFrozen
+		);
+		assert_eq!(
+			<Token as Inspect<u64>>::can_withdraw(currency_code, &BOB_ACCOUNT_ID, 500),
+			WithdrawConsequence::Frozen
+		);
+
+		assert_noop!(
+			Token::thaw(Origin::signed(BOB_ACCOUNT_ID), currency_code, DAVE, 3),
+			Error::<Test>::NotFrozen
+		);
+		assert_ok!(Token::thaw(Origin::root(), currency_code, BOB, 3));
+		System::assert_last_event(Event::Token(crate::Event::Thawed {
+			currency_code,
+			did: BOB,
+			reason: 3,
+		}));
+		assert_eq!(Token::frozen(currency_code, BOB), None);
+		assert_ok!(Token::transfer_token(Origin::signed(BOB_ACCOUNT_ID), transfer_vc_id, DAVE, 500));
+		assert_eq!(Token::token_balance(currency_code, DAVE), 500);
+	});
+}
+
+#[test]
+fn test_registered_token_vc_is_not_pruned() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, 1_000);
+		assert_eq!(Token::token_vc(token_vc_id), Some(currency_code));
+
+		// the used and deactivated TokenVC outlives the retention period
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), token_vc_id, false));
+		for block in 11..14 {
+			VC::on_idle(block, Weight::max_value());
+		}
+		assert!(VC::get_vc(&token_vc_id).is_some());
+		assert_eq!(VC::get_vc_owner(&token_vc_id), Some(BOB));
+
+		// the owner of the TokenVC is still the admin of the token
+		assert_ok!(Token::vested_transfer(
+			Origin::signed(BOB_ACCOUNT_ID),
+			currency_code,
+			DAVE,
+			100,
+			1,
+			0,
+			10
+		));
+		assert_ok!(Token::freeze(Origin::signed(BOB_ACCOUNT_ID), currency_code, DAVE, 1));
+	});
+}
+
+#[test]
+fn test_redemption() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		mint_tokens(currency_code, DAVE_ACCOUNT_ID, 1_000);
+
+		assert_noop!(
+			Token::request_redemption(Origin::signed(DAVE_ACCOUNT_ID), currency_code, 1_001),
+			Error::<Test>::BalanceTooLow
+		);
+		assert_noop!(
+			Token::request_redemption(Origin::signed(DAVE_ACCOUNT_ID), currency_code, 0),
+			Error::<Test>::ZeroAmount
+		);
+
+		// the redeemed tokens are held while the request is pending
+		assert_ok!(Token::request_redemption(Origin::signed(DAVE_ACCOUNT_ID), currency_code, 600));
+		assert_ok!(Token::request_redemption(Origin::signed(DAVE_ACCOUNT_ID), currency_code, 300));
+		System::assert_last_event(Event::Token(crate::Event::RedemptionRequested {
+			id: 1,
+			currency_code,
+			holder: DAVE,
+			amount: 300,
+		}));
+		assert_eq!(Token::token_balance(currency_code, DAVE), 100);
+		assert_eq!(Token::held_balance(currency_code, DAVE), 900);
+		assert_eq!(Token::total_issuance(currency_code), 1_000);
+		assert_eq!(
+			Token::redemption(0),
+			Some(Redemption { currency_code, holder: DAVE, amount: 600 })
+		);
+
+		// only the token owner or the governance closes the requests
+		assert_noop!(
+			Token::settle_redemption(Origin::signed(DAVE_ACCOUNT_ID), 0),
+			Error::<Test>::NotTokenOwner
+		);
+		assert_ok!(Token::settle_redemption(Origin::signed(BOB_ACCOUNT_ID), 0));
+		assert_eq!(Token::redemption(0), None);
+		assert_eq!(Token::held_balance(currency_code, DAVE), 300);
+		assert_eq!(Token::total_issuance(currency_code), 400);
+		assert_eq!(Token::token_info(currency_code).unwrap().total_burned, 600);
+
+		assert_ok!(Token::reject_redemption(Origin::root(), 1, 7));
+		System::assert_last_event(Event::Token(crate::Event::RedemptionRejected {
+			id: 1,
+			currency_code,
+			holder: DAVE,
+			amount: 300,
+			reason: 7,
+		}));
+		assert_eq!(Token::redemption(1), None);
+		assert_eq!(Token::token_balance(currency_code, DAVE), 400);
+		assert_eq!(Token::held_balance(currency_code, DAVE), 0);
+
+		assert_noop!(
+			Token::settle_redemption(Origin::root(), 1),
+			Error::<Test>::RedemptionNotFound
+		);
+		assert_noop!(
+			Token::reject_redemption(Origin::root(), 2, 0),
+			Error::<Test>::RedemptionNotFound
+		);
+
+		// frozen holdings can not be redeemed
+		assert_ok!(Token::freeze(Origin::root(), currency_code, DAVE, 1));
+		assert_noop!(
+			Token::request_redemption(Origin::signed(DAVE_ACCOUNT_ID), currency_code, 100),
+			Error::<Test>::HoldingFrozen
+		);
+	});
+}
+
+#[test]
+fn test_vesting() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, 2_000);
+
+		// only the token owner can vest tokens
+		assert_noop!(
+			Token::vested_transfer(
+				Origin::signed(DAVE_ACCOUNT_ID),
+				currency_code,
+				BOB,
+				1_000,
+				10,
+				5,
+				20,
+			),
+			Error::<Test>::NotTokenOwner
+		);
+		assert_noop!(
+			Token::vested_transfer(
+				Origin::signed(BOB_ACCOUNT_ID),
+				currency_code,
+				DAVE,
+				1_000,
+				10,
+				21,
+				20,
+			),
+			Error::<Test>::InvalidVestingSchedule
+		);
+		assert_ok!(Token::vested_transfer(
+			Origin::signed(BOB_ACCOUNT_ID),
+			currency_code,
+			DAVE,
+			1_000,
+			10,
+			5,
+			20
+		));
+		assert_eq!(Token::token_balance(currency_code, DAVE), 1_000);
+		assert_eq!(Token::vesting_locked(currency_code, DAVE), 1_000);
+
+		// locked tokens can not be moved
+		assert_noop!(
+			<Token as Transfer<u64>>::transfer(
+				currency_code,
+				&DAVE_ACCOUNT_ID,
+				&BOB_ACCOUNT_ID,
+				1,
+				true,
+			),
+			Error::<Test>::BalanceLocked
+		);
+		assert_eq!(
+			<Token as Inspect<u64>>::can_withdraw(currency_code, &DAVE_ACCOUNT_ID, 1),
+			WithdrawConsequence::Frozen
+		);
+
+		// nothing vests before the cliff
+		System::set_block_number(14);
+		assert_eq!(
+			Token::vesting_balance(currency_code, DAVE),
+			VestingBalance { locked: 1_000, vested: 0 }
+		);
+		assert_noop!(
+			Token::claim_vested(Origin::signed(DAVE_ACCOUNT_ID), currency_code),
+			Error::<Test>::NothingToClaim
+		);
+
+		System::set_block_number(15);
+		assert_eq!(
+			Token::vesting_balance(currency_code, DAVE),
+			VestingBalance { locked: 1_000, vested: 250 }
+		);
+		assert_ok!(Token::claim_vested(Origin::signed(DAVE_ACCOUNT_ID), currency_code));
+		System::assert_last_event(Event::Token(crate::Event::VestingClaimed {
+			currency_code,
+			did: DAVE,
+			amount: 250,
+		}));
+		assert_eq!(Token::vesting_locked(currency_code, DAVE), 750);
+		assert_ok!(<Token as Transfer<u64>>::transfer(
+			currency_code,
+			&DAVE_ACCOUNT_ID,
+			&BOB_ACCOUNT_ID,
+			250,
+			true
+		));
+		assert_noop!(
+			<Token as Transfer<u64>>::transfer(
+				currency_code,
+				&DAVE_ACCOUNT_ID,
+				&BOB_ACCOUNT_ID,
+				1,
+				true,
+			),
+			Error::<Test>::BalanceLocked
+		);
+
+		// the schedule is dropped once fully claimed
+		System::set_block_number(30);
+		assert_ok!(Token::claim_vested(Origin::signed(DAVE_ACCOUNT_ID), currency_code));
+		assert_eq!(Token::vesting_locked(currency_code, DAVE), 0);
+		assert!(Token::vesting(currency_code, DAVE).is_empty());
+		assert_eq!(Token::token_balance(currency_code, DAVE), 750);
+
+		for _ in 0..3 {
+			assert_ok!(Token::vested_transfer(
+				Origin::signed(BOB_ACCOUNT_ID),
+				currency_code,
+				DAVE,
+				10,
+				40,
+				0,
+				10
+			));
+		}
+		assert_noop!(
+			Token::vested_transfer(
+				Origin::signed(BOB_ACCOUNT_ID),
+				currency_code,
+				DAVE,
+				10,
+				40,
+				0,
+				10,
+			),
+			Error::<Test>::TooManyVestingSchedules
+		);
+	});
+}
+
+#[test]
+fn test_escrow() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, 1_000);
+		let create_escrow = |amount: u64, arbiter: Option<Did>, deadline: u64| {
+			Token::create_escrow(
+				Origin::signed(BOB_ACCOUNT_ID),
+				currency_code,
+				DAVE,
+				arbiter,
+				amount,
+				deadline,
+			)
+		};
+
+		assert_noop!(create_escrow(300, None, 1), Error::<Test>::InvalidDeadline);
+		assert_noop!(create_escrow(1_001, None, 10), Error::<Test>::BalanceTooLow);
+
+		// the recipient accepts the tokens
+		assert_ok!(create_escrow(300, None, 10));
+		System::assert_last_event(Event::Token(crate::Event::EscrowCreated {
+			id: 0,
+			currency_code,
+			sender: BOB,
+			recipient: DAVE,
+			amount: 300,
+			deadline: 10,
+		}));
+		assert_eq!(Token::held_balance(currency_code, BOB), 300);
+		assert_noop!(
+			Token::release_escrow(Origin::signed(BOB_ACCOUNT_ID), 0),
+			Error::<Test>::NotAllowedToSettle
+		);
+		assert_noop!(
+			Token::refund_escrow(Origin::signed(BOB_ACCOUNT_ID), 0),
+			Error::<Test>::NotAllowedToSettle
+		);
+		assert_ok!(Token::release_escrow(Origin::signed(DAVE_ACCOUNT_ID), 0));
+		assert_eq!(Token::token_balance(currency_code, DAVE), 300);
+		assert_eq!(Token::held_balance(currency_code, BOB), 0);
+		assert_eq!(Token::escrow(0), None);
+		assert!(Token::escrows_of(BOB).is_empty());
+
+		// escrows are bounded per sender
+		assert_ok!(create_escrow(200, None, 10));
+		assert_ok!(create_escrow(100, None, 10));
+		assert_noop!(create_escrow(100, None, 20), Error::<Test>::TooManyEscrows);
+
+		// the recipient declines the tokens
+		assert_ok!(Token::refund_escrow(Origin::signed(DAVE_ACCOUNT_ID), 1));
+		assert_eq!(Token::token_balance(currency_code, BOB), 600);
+
+		// the escrow is refunded at its deadline
+		Token::on_initialize(10);
+		System::assert_last_event(Event::Token(crate::Event::EscrowRefunded {
+			id: 2,
+			currency_code,
+			sender: BOB,
+			amount: 100,
+		}));
+		assert_eq!(Token::token_balance(currency_code, BOB), 700);
+		assert_eq!(Token::escrow(2), None);
+		assert!(Token::escrows_of(BOB).is_empty());
+
+		// only the arbiter arbitrates
+		System::set_block_number(11);
+		assert_ok!(create_escrow(100, Some(DAVE), 20));
+		assert_noop!(
+			Token::arbitrate_escrow(Origin::signed(BOB_ACCOUNT_ID), 3, true),
+			Error::<Test>::NotAllowedToSettle
+		);
+		assert_ok!(Token::arbitrate_escrow(Origin::signed(DAVE_ACCOUNT_ID), 3, false));
+		assert_eq!(Token::token_balance(currency_code, BOB), 700);
+
+		// the deadlines are bounded per block
+		assert_ok!(create_escrow(100, None, 20));
+		assert_ok!(create_escrow(100, None, 20));
+		assert_noop!(
+			Token::create_escrow(
+				Origin::signed(DAVE_ACCOUNT_ID),
+				currency_code,
+				BOB,
+				None,
+				100,
+				20,
+			),
+			Error::<Test>::TooManyEscrowExpiries
+		);
+
+		// the sender refunds an expired escrow
+		System::set_block_number(20);
+		assert_ok!(Token::refund_escrow(Origin::signed(BOB_ACCOUNT_ID), 4));
+		assert_eq!(Token::token_balance(currency_code, BOB), 600);
+
+		// the tokens are not released while either holding is frozen
+		assert_ok!(Token::freeze(Origin::root(), currency_code, DAVE, 1));
+		assert_noop!(
+			Token::release_escrow(Origin::signed(DAVE_ACCOUNT_ID), 5),
+			Error::<Test>::HoldingFrozen
+		);
+		assert_ok!(Token::thaw(Origin::root(), currency_code, DAVE, 1));
+		assert_ok!(Token::freeze(Origin::root(), currency_code, BOB, 1));
+		assert_noop!(
+			Token::release_escrow(Origin::signed(DAVE_ACCOUNT_ID), 5),
+			Error::<Test>::HoldingFrozen
+		);
+		assert_ok!(Token::refund_escrow(Origin::signed(DAVE_ACCOUNT_ID), 5));
+		assert_eq!(Token::held_balance(currency_code, BOB), 0);
+		assert_eq!(Token::token_balance(currency_code, BOB), 700);
+	});
+}
+
+#[test]
+fn test_allowances() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_token_vc("OTH", 1_000);
+		register_token(token_vc_id);
+		mint_tokens(currency_code, BOB_ACCOUNT_ID, 1_000);
+
+		assert_noop!(
+			Token::approve(Origin::signed(BOB_ACCOUNT_ID), *b"NONE\0\0\0\0", DAVE, 100),
+			Error::<Test>::TokenNotRegistered
+		);
+		assert_noop!(
+			Token::approve(Origin::signed(BOB_ACCOUNT_ID), currency_code, ALICE, 100),
+			Error::<Test>::DIDDoesNotExist
+		);
+
+		assert_ok!(Token::approve(Origin::signed(BOB_ACCOUNT_ID), currency_code, DAVE, 300));
+		assert_ok!(Token::increase_allowance(
+			Origin::signed(BOB_ACCOUNT_ID),
+			currency_code,
+			DAVE,
+			200
+		));
+		assert_ok!(Token::decrease_allowance(
+			Origin::signed(BOB_ACCOUNT_ID),
+			currency_code,
+			DAVE,
+			100
+		));
+		System::assert_last_event(Event::Token(crate::Event::AllowanceSet {
+			currency_code,
+			owner: BOB,
+			spender: DAVE,
+			amount: 400,
+		}));
+		assert_eq!(Token::allowance((BOB, DAVE, currency_code)), 400);
+		assert_noop!(
+			Token::decrease_allowance(Origin::signed(BOB_ACCOUNT_ID), currency_code, DAVE, 401),
+			Error::<Test>::AllowanceTooLow
+		);
+
+		// the spender pulls the tokens with its own key
+		assert_noop!(
+			Token::transfer_from(Origin::signed(DAVE_ACCOUNT_ID), currency_code, BOB, DAVE, 401),
+			Error::<Test>::AllowanceTooLow
+		);
+		assert_ok!(Token::transfer_from(
+			Origin::signed(DAVE_ACCOUNT_ID),
+			currency_code,
+			BOB,
+			DAVE,
+			150
+		));
+		System::assert_last_event(Event::Token(crate::Event::TransferredFrom {
+			currency_code,
+			owner: BOB,
+			spender: DAVE,
+			to: DAVE,
+			amount: 150,
+		}));
+		assert_eq!(Token::token_balance(currency_code, DAVE), 150);
+		assert_eq!(Token::allowance((BOB, DAVE, currency_code)), 250);
+
+		// the owner can not be drawn from while its holding is frozen
+		assert_ok!(Token::freeze(Origin::root(), currency_code, BOB, 1));
+		assert_noop!(
+			Token::transfer_from(Origin::signed(DAVE_ACCOUNT_ID), currency_code, BOB, DAVE, 250),
+			Error::<Test>::HoldingFrozen
+		);
+		assert_ok!(Token::thaw(Origin::root(), currency_code, BOB, 1));
+
+		assert_ok!(Token::transfer_from(
+			Origin::signed(DAVE_ACCOUNT_ID),
+			currency_code,
+			BOB,
+			DAVE,
+			250
+		));
+		assert_eq!(Token::allowance((BOB, DAVE, currency_code)), 0);
+		assert_ok!(Token::approve(Origin::signed(BOB_ACCOUNT_ID), currency_code, DAVE, 0));
+		assert_eq!(Token::token_balance(currency_code, BOB), 600);
+	});
+}
+
+#[test]
+fn test_token_indices() {
+	new_test_ext().execute_with(|| {
+		let oth: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let xyz: CurrencyCode = convert_to_array::<8>("XYZ".into());
+		register_token(store_token_vc("OTH", 500));
+		register_token(store_token_vc("XYZ", 500));
+		mint_tokens(oth, BOB_ACCOUNT_ID, 1_000);
+		mint_tokens(xyz, BOB_ACCOUNT_ID, 300);
+
+		assert_ok!(<Token as Transfer<u64>>::transfer(
+			oth,
+			&BOB_ACCOUNT_ID,
+			&DAVE_ACCOUNT_ID,
+			400,
+			false
+		));
+		let mut balances = Token::balances_of(BOB);
+		balances.sort();
+		let mut expected = vec![(oth, 600, 0), (xyz, 300, 0)];
+		expected.sort();
+		assert_eq!(balances, expected);
+		assert_eq!(Token::balances_of(DAVE), vec![(oth, 400, 0)]);
+
+		let mut holders = Token::holders(oth, 0, 10);
+		holders.sort();
+		let mut expected = vec![(BOB, 600, 0), (DAVE, 400, 0)];
+		expected.sort();
+		assert_eq!(holders, expected);
+		assert_eq!(Token::holders(oth, 0, 1).len(), 1);
+		assert_eq!(Token::holders(oth, 1, 10).len(), 1);
+		assert!(Token::holders(oth, 2, 10).is_empty());
+
+		// held tokens keep their holder indexed
+		assert_ok!(Token::request_redemption(Origin::signed(DAVE_ACCOUNT_ID), oth, 400));
+		assert_eq!(Token::balances_of(DAVE), vec![(oth, 0, 400)]);
+		assert_eq!(Token::holders(oth, 1, 10), vec![(DAVE, 0, 400)]);
+		assert_ok!(Token::reject_redemption(Origin::signed(BOB_ACCOUNT_ID), 0, 0));
+		assert_eq!(Token::balances_of(DAVE), vec![(oth, 400, 0)]);
+
+		// holdings emptied by a transfer or a burn leave the indices
+		assert_ok!(<Token as Transfer<u64>>::transfer(
+			oth,
+			&DAVE_ACCOUNT_ID,
+			&BOB_ACCOUNT_ID,
+			400,
+			false
+		));
+		assert_ok!(<Token as Mutate<u64>>::burn_from(xyz, &BOB_ACCOUNT_ID, 300));
+		assert_eq!(Token::balances_of(BOB), vec![(oth, 1_000, 0)]);
+		assert!(Token::balances_of(DAVE).is_empty());
+		assert_eq!(Token::holders(oth, 0, 10), vec![(BOB, 1_000, 0)]);
+		assert!(Token::holders(xyz, 0, 10).is_empty());
+	});
+}
+
+#[test]
+fn test_migrate_to_v1() {
+	new_test_ext().execute_with(|| {
+		let currency_code: CurrencyCode = convert_to_array::<8>("OTH".into());
+		let token_vc_id = store_capped_token_vc("OTH", 1_000, Some(5_000));
+		let _ = Balances::deposit_creating(&BOB_ACCOUNT_ID, 2_000);
+		assert_ok!(Balances::reserve(&BOB_ACCOUNT_ID, 1_500));
+		StorageVersion::new(0).put::<Token>();
+
+		Token::on_runtime_upgrade();
+		assert_eq!(Token::on_chain_storage_version(), 1);
+		assert_eq!(
+			Token::token_info(currency_code),
+			Some(TokenInfo {
+				token_name: convert_to_array::<16>("test".into()),
+				decimal: 6,
+				issuer: BOB,
+				vc_id: token_vc_id,
+				reserve_account: BOB_ACCOUNT_ID,
+				reserved: 1_000,
+				total_minted: 0,
+				total_burned: 0,
+				max_supply: Some(5_000),
+			})
+		);
+		assert_eq!(Token::token_vc(token_vc_id), Some(currency_code));
+		assert_eq!(Token::total_issuance(currency_code), 0);
+
+		// the reservable balance of the token is moved under its currency code
+		assert_eq!(Balances::reserved_balance_named(&currency_code, &BOB_ACCOUNT_ID), 1_000);
+		assert_eq!(Balances::reserved_balance(&BOB_ACCOUNT_ID), 1_500);
+		assert_ok!(Token::withdraw_reserved(
+			Origin::signed(BOB_ACCOUNT_ID),
+			DAVE,
+			currency_code,
+			400
+		));
+
+		// a second run leaves the tokens as they are
+		Token::on_runtime_upgrade();
+		assert_eq!(Token::token_info(currency_code).unwrap().reserved, 600);
+	});
+}
@@ -1,6 +1,23 @@
-use frame_support::{RuntimeDebug};
+use frame_support::{
+	sp_runtime::{
+		helpers_128bit::multiply_by_rational,
+		traits::{AtLeast32BitUnsigned, Saturating, Zero},
+		SaturatedConversion,
+	},
+	RuntimeDebug,
+};
 use scale_info::TypeInfo;
 use codec::{Decode, Encode, MaxEncodedLen};
+use metamui_primitives::{types::CurrencyCode, Did, VCid};
+
+/// Code recording why a holding is frozen or thawed, defined by the token issuer.
+pub type ReasonCode = u8;
+
+/// Id of a redemption request
+pub type RedemptionId = u64;
+
+/// Id of an escrowed transfer
+pub type EscrowId = u64;
 
 /// Type used to encode the number of references an account has.
 pub type RefCount = u32;
@@ -22,4 +39,116 @@ pub struct AccountInfo<Index, AccountData> {
 	/// The additional data that belongs to this account. Used to store the balance(s) in a lot of
 	/// chains.
 	pub data: AccountData,
-}
\ No newline at end of file
+}
+
+/// A token registered from a `TokenVC`
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, Encode, Decode, MaxEncodedLen)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TokenInfo<AccountId, Balance> {
+	/// Name of the token
+	pub token_name: [u8; 16],
+	/// Number of decimals of the token
+	pub decimal: u8,
+	/// Did of the issuer who registered the token
+	pub issuer: Did,
+	/// The `TokenVC` the token was created from
+	///
+	/// The terms of the token are the ones of this VC when the token was registered. A VC
+	/// superseding it takes over the ownership of the token, not its terms.
+	pub vc_id: VCid,
+	/// Account the reserve was made from when the token was registered, it holds the reserve
+	/// even if the issuer rotates its key
+	pub reserve_account: AccountId,
+	/// Amount left of the reserve made from the issuer when the token was registered
+	pub reserved: Balance,
+	/// Total amount of the token minted
+	pub total_minted: Balance,
+	/// Total amount of the token burned
+	pub total_burned: Balance,
+	/// Maximum circulating supply of the token, `None` if the supply is not capped
+	pub max_supply: Option<Balance>,
+}
+
+/// A request of a holder to redeem its tokens with the issuer
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, Encode, Decode, MaxEncodedLen)]
+pub struct Redemption<Balance> {
+	/// Currency code of the redeemed token
+	pub currency_code: CurrencyCode,
+	/// Did of the holder redeeming the tokens
+	pub holder: Did,
+	/// Amount of the token redeemed
+	pub amount: Balance,
+}
+
+/// Tokens of a Did released gradually, they vest linearly from the start block
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, Encode, Decode, MaxEncodedLen)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	/// Amount locked under the schedule
+	pub amount: Balance,
+	/// Block from which the amount vests
+	pub start: BlockNumber,
+	/// Number of blocks from the start before anything vests, what vested until then unlocks at
+	/// once
+	pub cliff: BlockNumber,
+	/// Number of blocks from the start until the whole amount is vested
+	pub duration: BlockNumber,
+	/// Amount of the vested tokens already claimed
+	pub claimed: Balance,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+	Balance: AtLeast32BitUnsigned + Copy,
+	BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+	/// Amount vested at the block, claimed or not
+	pub fn vested_at(&self, now: BlockNumber) -> Balance {
+		if now < self.start.saturating_add(self.cliff) {
+			return Zero::zero()
+		}
+		let elapsed = now.saturating_sub(self.start);
+		if elapsed >= self.duration {
+			return self.amount
+		}
+		multiply_by_rational(
+			self.amount.saturated_into(),
+			elapsed.saturated_into(),
+			self.duration.saturated_into(),
+		)
+		.map(|vested| vested.saturated_into())
+		.unwrap_or(self.amount)
+	}
+
+	/// Amount still locked, vested tokens are locked until they are claimed
+	pub fn locked(&self) -> Balance {
+		self.amount.saturating_sub(self.claimed)
+	}
+}
+
+/// Vesting of the tokens of a Did
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, TypeInfo, Encode, Decode)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VestingBalance<Balance> {
+	/// Amount locked by the vesting schedules
+	pub locked: Balance,
+	/// Part of the locked amount that is vested and can be claimed
+	pub vested: Balance,
+}
+
+/// Tokens held from the sender until they are released to the recipient or refunded
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, Encode, Decode, MaxEncodedLen)]
+pub struct Escrow<Balance, BlockNumber> {
+	/// Currency code of the escrowed token
+	pub currency_code: CurrencyCode,
+	/// Did the tokens are held from
+	pub sender: Did,
+	/// Did the tokens are released to
+	pub recipient: Did,
+	/// Did that can settle the escrow either way
+	pub arbiter: Option<Did>,
+	/// Amount of the token held
+	pub amount: Balance,
+	/// Block at which the tokens are refunded if they are not released
+	pub deadline: BlockNumber,
+}
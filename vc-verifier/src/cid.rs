@@ -0,0 +1,195 @@
+//! Content identifiers (CID) of the off-chain documents referenced by `GenericVC`
+//!
+//! A CIDv0 is the base58btc encoding of a sha2-256 multihash. A CIDv1 is a multibase string of
+//! the version, the multicodec of the content and the multihash, each prefixed by an unsigned
+//! varint. The parsed digest lets a verifier fetch the document and check it against the VC.
+//!
+//! The CID is stored in 64 bytes, which only fits the multihash of a 32 byte digest. The hash
+//! functions with a longer digest, like sha2-512, are not supported.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Multicodec of the content of a CIDv0
+pub const DAG_PB: u64 = 0x70;
+/// Multihash code of sha2-256
+pub const SHA2_256: u64 = 0x12;
+/// Multihash code of sha3-256
+pub const SHA3_256: u64 = 0x16;
+/// Multihash code of blake3
+pub const BLAKE3: u64 = 0x1e;
+/// Multihash code of blake2b-256
+pub const BLAKE2B_256: u64 = 0xb220;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32_LOWER: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const BASE32_UPPER: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Reasons for which a CID is rejected
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CidError {
+	/// The CID is empty
+	Empty,
+	/// The multibase prefix is not supported
+	UnsupportedBase,
+	/// The CID contains a character outside of its base
+	InvalidCharacter,
+	/// The trailing bits of a base32 CID do not form a canonical encoding
+	NonCanonicalEncoding,
+	/// A varint is truncated, not minimal or too long
+	InvalidVarint,
+	/// The CID version is not 0 or 1
+	UnsupportedVersion,
+	/// The multihash function is not supported
+	UnsupportedHash,
+	/// The digest length does not match the multihash function
+	InvalidDigestLength,
+}
+
+/// A parsed CID
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cid {
+	/// Version of the CID, 0 or 1
+	pub version: u8,
+	/// Multicodec of the content
+	pub codec: u64,
+	/// Multihash code of the hash function
+	pub hash_code: u64,
+	/// Digest of the content
+	pub digest: Vec<u8>,
+}
+
+/// Parse a CID string, trailing zero bytes are padding and ignored
+///
+/// Supported multibases are base58btc (`z`), base32 (`b`, `B`) and base16 (`f`, `F`).
+pub fn parse_cid(cid: &[u8]) -> Result<Cid, CidError> {
+	let end = cid.iter().rposition(|byte| *byte != 0).map_or(0, |i| i + 1);
+	let cid = &cid[..end];
+	if cid.is_empty() {
+		return Err(CidError::Empty)
+	}
+
+	if cid.len() == 46 && cid.starts_with(b"Qm") {
+		let (hash_code, digest) = parse_multihash(&decode_base58(cid)?)?;
+		if hash_code != SHA2_256 {
+			return Err(CidError::UnsupportedHash)
+		}
+		return Ok(Cid { version: 0, codec: DAG_PB, hash_code, digest })
+	}
+
+	let bytes = match cid[0] {
+		b'z' => decode_base58(&cid[1..])?,
+		b'b' => decode_base32(&cid[1..], BASE32_LOWER)?,
+		b'B' => decode_base32(&cid[1..], BASE32_UPPER)?,
+		b'f' | b'F' => decode_base16(&cid[1..])?,
+		_ => return Err(CidError::UnsupportedBase),
+	};
+	let mut input = &bytes[..];
+	if read_varint(&mut input)? != 1 {
+		return Err(CidError::UnsupportedVersion)
+	}
+	let codec = read_varint(&mut input)?;
+	let (hash_code, digest) = parse_multihash(input)?;
+	Ok(Cid { version: 1, codec, hash_code, digest })
+}
+
+/// Length of the digest of the supported hash functions
+fn digest_len(hash_code: u64) -> Option<usize> {
+	match hash_code {
+		SHA2_256 | SHA3_256 | BLAKE3 | BLAKE2B_256 => Some(32),
+		_ => None,
+	}
+}
+
+/// Split a multihash into its hash code and digest
+fn parse_multihash(mut input: &[u8]) -> Result<(u64, Vec<u8>), CidError> {
+	let hash_code = read_varint(&mut input)?;
+	let len = read_varint(&mut input)?;
+	let expected = digest_len(hash_code).ok_or(CidError::UnsupportedHash)?;
+	if len != expected as u64 || input.len() != expected {
+		return Err(CidError::InvalidDigestLength)
+	}
+	Ok((hash_code, input.to_vec()))
+}
+
+/// Read an unsigned varint, at most 9 bytes long as in the multiformats spec
+fn read_varint(input: &mut &[u8]) -> Result<u64, CidError> {
+	let mut value: u64 = 0;
+	for i in 0..9 {
+		let (&byte, rest) = input.split_first().ok_or(CidError::InvalidVarint)?;
+		*input = rest;
+		value |= u64::from(byte & 0x7f) << (7 * i);
+		if byte & 0x80 == 0 {
+			// the encoding must be minimal
+			if byte == 0 && i > 0 {
+				return Err(CidError::InvalidVarint)
+			}
+			return Ok(value)
+		}
+	}
+	Err(CidError::InvalidVarint)
+}
+
+fn decode_base58(input: &[u8]) -> Result<Vec<u8>, CidError> {
+	// little endian while decoding
+	let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+	for c in input.iter() {
+		let mut carry = BASE58_ALPHABET
+			.iter()
+			.position(|a| a == c)
+			.ok_or(CidError::InvalidCharacter)? as u32;
+		for byte in bytes.iter_mut() {
+			carry += u32::from(*byte) * 58;
+			*byte = carry as u8;
+			carry >>= 8;
+		}
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8;
+		}
+	}
+	// each leading '1' is a leading zero byte
+	let zeros = input.iter().take_while(|c| **c == b'1').count();
+	bytes.resize(bytes.len() + zeros, 0);
+	bytes.reverse();
+	Ok(bytes)
+}
+
+fn decode_base32(input: &[u8], alphabet: &[u8; 32]) -> Result<Vec<u8>, CidError> {
+	let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+	let mut buffer: u32 = 0;
+	let mut bits: u32 = 0;
+	for c in input.iter() {
+		let value =
+			alphabet.iter().position(|a| a == c).ok_or(CidError::InvalidCharacter)? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+		}
+		buffer &= (1 << bits) - 1;
+	}
+	// the last character may only carry the remaining zero bits of the last byte
+	if bits >= 5 || buffer != 0 {
+		return Err(CidError::NonCanonicalEncoding)
+	}
+	Ok(bytes)
+}
+
+fn decode_base16(input: &[u8]) -> Result<Vec<u8>, CidError> {
+	if input.len() % 2 != 0 {
+		return Err(CidError::InvalidCharacter)
+	}
+	input
+		.chunks(2)
+		.map(|pair| {
+			let high = (pair[0] as char).to_digit(16).ok_or(CidError::InvalidCharacter)?;
+			let low = (pair[1] as char).to_digit(16).ok_or(CidError::InvalidCharacter)?;
+			Ok((high * 16 + low) as u8)
+		})
+		.collect()
+}
@@ -0,0 +1,210 @@
+//! Verification of MetaMUI Verifiable Credentials
+//!
+//! The VC hash rule and the issuer signature checks are defined here once and used both by
+//! `pallet-vc` in the runtime and by off-chain services, so the two cannot drift apart.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Encode;
+use metamui_primitives::{
+	types::{PublicKey, VCExtension, VCProperty, VCType, VC},
+	Did,
+};
+#[cfg(feature = "full_crypto")]
+use sp_core::Pair;
+use sp_runtime::{traits::Hash, MultiSignature, RuntimeDebug};
+use sp_std::prelude::*;
+
+pub mod cid;
+
+#[cfg(test)]
+mod tests;
+
+/// Reasons for which a VC is rejected before its signatures are counted
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum VerifyError {
+	/// The hash in the VC does not match its properties
+	HashMismatch,
+	/// An issuer is listed more than once
+	DuplicateIssuer,
+	/// A signature is included more than once
+	DuplicateSignature,
+}
+
+/// Canonical bytes of the VC properties, the VC hash is the hash of these bytes
+///
+/// Single use VCs keep the original layout without `max_uses`, so signatures made before usage
+/// counters were introduced stay valid.
+pub fn payload(
+	vc_type: &VCType,
+	vc_property: &VCProperty,
+	owner: &Did,
+	issuers: &[Did],
+	max_uses: Option<u32>,
+) -> Vec<u8> {
+	if max_uses == Some(1) {
+		(vc_type, vc_property, owner, issuers).encode()
+	} else {
+		(vc_type, vc_property, owner, issuers, max_uses).encode()
+	}
+}
+
+/// Canonical hash of the VC properties, this is what the issuers sign
+pub fn vc_hash<H: Hash>(
+	vc_type: &VCType,
+	vc_property: &VCProperty,
+	owner: &Did,
+	issuers: &[Did],
+	max_uses: Option<u32>,
+) -> H::Output {
+	H::hash(&payload(vc_type, vc_property, owner, issuers, max_uses))
+}
+
+/// Canonical hash of an existing VC, computed from its properties
+pub fn hash_of_vc<H: Hash>(vc: &VC<H::Output>) -> H::Output {
+	vc_hash::<H>(&vc.vc_type, &vc.vc_property, &vc.owner, &vc.issuers, vc.max_uses)
+}
+
+/// Canonical hash of a VC with the extension of a newer VC format, the extension is appended to
+/// the v1 payload so the issuers sign it too
+pub fn hash_of_extended_vc<H: Hash>(vc: &VC<H::Output>, extension: &VCExtension) -> H::Output {
+	let mut payload = payload(&vc.vc_type, &vc.vc_property, &vc.owner, &vc.issuers, vc.max_uses);
+	extension.encode_to(&mut payload);
+	H::hash(&payload)
+}
+
+/// Build an unsigned VC with its canonical hash
+pub fn build_vc<H: Hash>(
+	vc_type: VCType,
+	vc_property: VCProperty,
+	owner: Did,
+	issuers: Vec<Did>,
+	max_uses: Option<u32>,
+) -> VC<H::Output> {
+	let hash = vc_hash::<H>(&vc_type, &vc_property, &owner, &issuers, max_uses);
+	VC {
+		hash,
+		owner,
+		issuers,
+		signatures: Vec::new(),
+		max_uses,
+		used_count: 0,
+		is_vc_active: false,
+		vc_type,
+		vc_property,
+	}
+}
+
+/// Sign a VC hash with the given keypair
+#[cfg(feature = "full_crypto")]
+pub fn sign<P, Hash>(pair: &P, hash: &Hash) -> MultiSignature
+where
+	P: Pair,
+	P::Signature: Into<MultiSignature>,
+	Hash: AsRef<[u8]>,
+{
+	pair.sign(hash.as_ref()).into()
+}
+
+/// Sign the VC with the given keypair and append the signature to it
+#[cfg(feature = "full_crypto")]
+pub fn sign_vc<P, Hash>(pair: &P, vc: &mut VC<Hash>)
+where
+	P: Pair,
+	P::Signature: Into<MultiSignature>,
+	Hash: AsRef<[u8]>,
+{
+	let signature = sign(pair, &vc.hash);
+	vc.signatures.push(signature);
+}
+
+/// Check the signature on the VC hash against an issuer key
+///
+/// The signature scheme has to match the scheme of the key.
+pub fn verify_signature<Hash: AsRef<[u8]>>(
+	public_key: &PublicKey,
+	signature: &MultiSignature,
+	hash: &Hash,
+) -> bool {
+	public_key.verify_signature(signature, hash.as_ref())
+}
+
+/// Check that the hash matches the VC properties and that there are no duplicate issuers or
+/// signatures
+///
+/// The property of a `GenericVC` holds the CID of the off-chain document, so the document is
+/// bound to the hash like the properties of any other VC.
+pub fn check_vc<H: Hash>(vc: &VC<H::Output>) -> Result<(), VerifyError> {
+	check_extended_vc::<H>(vc, None)
+}
+
+/// Same as `check_vc`, for a VC of a newer format with its extension
+pub fn check_extended_vc<H: Hash>(
+	vc: &VC<H::Output>,
+	extension: Option<&VCExtension>,
+) -> Result<(), VerifyError> {
+	let hash = match extension {
+		Some(extension) => hash_of_extended_vc::<H>(vc, extension),
+		None => hash_of_vc::<H>(vc),
+	};
+	if vc.hash != hash {
+		return Err(VerifyError::HashMismatch)
+	}
+
+	check_duplicates(vc)
+}
+
+/// Check that there are no duplicate issuers or signatures, without checking the hash
+///
+/// Only meant for VCs whose hash was not bound to their properties when they were issued, like
+/// the `GenericVC`s issued before the CID of the document was part of the hash.
+pub fn check_duplicates<Hash>(vc: &VC<Hash>) -> Result<(), VerifyError> {
+	let mut issuers = vc.issuers.clone();
+	issuers.sort();
+	issuers.dedup();
+	if issuers.len() != vc.issuers.len() {
+		return Err(VerifyError::DuplicateIssuer)
+	}
+
+	for (i, signature) in vc.signatures.iter().enumerate() {
+		if vc.signatures[i + 1..].contains(signature) {
+			return Err(VerifyError::DuplicateSignature)
+		}
+	}
+
+	Ok(())
+}
+
+/// Check that every issuer has signed the VC
+///
+/// `issuer_keys` are the public keys of the VC issuers, in any order.
+pub fn is_fully_signed<Hash: AsRef<[u8]>>(vc: &VC<Hash>, issuer_keys: &[PublicKey]) -> bool {
+	if vc.issuers.len() != vc.signatures.len() {
+		return false
+	}
+
+	let verified_count: usize = issuer_keys
+		.iter()
+		.map(|public_key| {
+			vc.signatures
+				.iter()
+				.filter(|signature| verify_signature(public_key, signature, &vc.hash))
+				.count()
+		})
+		.sum();
+
+	verified_count == vc.signatures.len()
+}
+
+/// Verify a VC against the public keys of its issuers
+///
+/// Returns `Ok(true)` when the VC is well formed and signed by every issuer, `Ok(false)` when
+/// signatures are still missing.
+pub fn verify_vc<H: Hash>(
+	vc: &VC<H::Output>,
+	issuer_keys: &[PublicKey],
+) -> Result<bool, VerifyError> {
+	check_vc::<H>(vc)?;
+	Ok(is_fully_signed(vc, issuer_keys))
+}
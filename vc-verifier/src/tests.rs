@@ -0,0 +1,168 @@
+use super::*;
+use sp_core::{ecdsa, ed25519, sr25519, Pair, H256};
+use sp_runtime::traits::BlakeTwo256;
+
+const BOB: Did = *b"did:ssid:bob\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+const DAVE: Did = *b"did:ssid:dave\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+const EVE: Did = *b"did:ssid:eve\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+
+fn unsigned_vc(issuers: Vec<Did>, max_uses: Option<u32>) -> VC<H256> {
+	build_vc::<BlakeTwo256>(VCType::TokenVC, [1; 128], BOB, issuers, max_uses)
+}
+
+#[test]
+fn test_hash_matches_pallet_rule() {
+	let vc = unsigned_vc(vec![BOB, DAVE], Some(1));
+	let expected =
+		BlakeTwo256::hash_of(&(&vc.vc_type, &vc.vc_property, &vc.owner, &vc.issuers));
+	assert_eq!(vc.hash, expected);
+
+	let vc = unsigned_vc(vec![BOB, DAVE], Some(3));
+	let expected = BlakeTwo256::hash_of(&(
+		&vc.vc_type,
+		&vc.vc_property,
+		&vc.owner,
+		&vc.issuers,
+		&vc.max_uses,
+	));
+	assert_eq!(vc.hash, expected);
+}
+
+#[test]
+fn test_sign_and_verify_all_schemes() {
+	let bob_pair = sr25519::Pair::from_seed(&[1; 32]);
+	let dave_pair = ed25519::Pair::from_seed(&[2; 32]);
+	let eve_pair = ecdsa::Pair::from_seed(&[3; 32]);
+	let issuer_keys: Vec<PublicKey> =
+		vec![bob_pair.public().into(), dave_pair.public().into(), eve_pair.public().into()];
+
+	let mut vc = unsigned_vc(vec![BOB, DAVE, EVE], Some(1));
+	sign_vc(&bob_pair, &mut vc);
+	sign_vc(&dave_pair, &mut vc);
+	assert_eq!(verify_vc::<BlakeTwo256>(&vc, &issuer_keys), Ok(false));
+
+	sign_vc(&eve_pair, &mut vc);
+	assert_eq!(verify_vc::<BlakeTwo256>(&vc, &issuer_keys), Ok(true));
+}
+
+#[test]
+fn test_signature_scheme_mismatch() {
+	let sr_pair = sr25519::Pair::from_seed(&[1; 32]);
+	let ed_pair = ed25519::Pair::from_seed(&[1; 32]);
+	let vc = unsigned_vc(vec![BOB], Some(1));
+
+	let signature = sign(&ed_pair, &vc.hash);
+	assert!(verify_signature(&ed_pair.public().into(), &signature, &vc.hash));
+	assert!(!verify_signature(&sr_pair.public().into(), &signature, &vc.hash));
+}
+
+#[test]
+fn test_signature_from_other_key() {
+	let bob_pair = sr25519::Pair::from_seed(&[1; 32]);
+	let eve_pair = sr25519::Pair::from_seed(&[3; 32]);
+
+	let mut vc = unsigned_vc(vec![BOB], Some(1));
+	sign_vc(&eve_pair, &mut vc);
+	assert_eq!(verify_vc::<BlakeTwo256>(&vc, &[bob_pair.public().into()]), Ok(false));
+}
+
+#[test]
+fn test_check_vc_errors() {
+	let pair = sr25519::Pair::from_seed(&[1; 32]);
+
+	let mut vc = unsigned_vc(vec![BOB], Some(1));
+	vc.max_uses = Some(2);
+	assert_eq!(check_vc::<BlakeTwo256>(&vc), Err(VerifyError::HashMismatch));
+
+	let vc = unsigned_vc(vec![BOB, BOB], Some(1));
+	assert_eq!(check_vc::<BlakeTwo256>(&vc), Err(VerifyError::DuplicateIssuer));
+
+	let mut vc = unsigned_vc(vec![BOB, DAVE], Some(1));
+	sign_vc(&pair, &mut vc);
+	vc.signatures.push(vc.signatures[0].clone());
+	assert_eq!(check_vc::<BlakeTwo256>(&vc), Err(VerifyError::DuplicateSignature));
+
+	// the hash of a generic VC covers its property like any other VC
+	let mut vc = unsigned_vc(vec![BOB], Some(1));
+	vc.vc_type = VCType::GenericVC;
+	assert_eq!(check_vc::<BlakeTwo256>(&vc), Err(VerifyError::HashMismatch));
+	assert_eq!(check_duplicates(&vc), Ok(()));
+	let vc = build_vc::<BlakeTwo256>(VCType::GenericVC, [1; 128], BOB, vec![BOB], Some(1));
+	assert_eq!(check_vc::<BlakeTwo256>(&vc), Ok(()));
+}
+
+#[test]
+fn test_extended_vc_hash() {
+	let extension = VCExtension { expires_at: Some(100), schema: None };
+	let mut vc = unsigned_vc(vec![BOB], Some(1));
+	assert_eq!(check_extended_vc::<BlakeTwo256>(&vc, None), Ok(()));
+	assert_eq!(
+		check_extended_vc::<BlakeTwo256>(&vc, Some(&extension)),
+		Err(VerifyError::HashMismatch)
+	);
+
+	let mut payload = (&vc.vc_type, &vc.vc_property, &vc.owner, &vc.issuers).encode();
+	payload.extend(extension.encode());
+	vc.hash = BlakeTwo256::hash(&payload);
+	assert_eq!(hash_of_extended_vc::<BlakeTwo256>(&vc, &extension), vc.hash);
+	assert_eq!(check_extended_vc::<BlakeTwo256>(&vc, Some(&extension)), Ok(()));
+	assert_eq!(check_vc::<BlakeTwo256>(&vc), Err(VerifyError::HashMismatch));
+
+	// the extension cannot be changed once signed
+	let extension = VCExtension { expires_at: Some(200), schema: None };
+	assert_eq!(
+		check_extended_vc::<BlakeTwo256>(&vc, Some(&extension)),
+		Err(VerifyError::HashMismatch)
+	);
+}
+
+const CID_V0: &[u8] = b"QmdQYGSHZDGk6jDwCP2Sy4ybFAdH5PUvbt1oCcZzS1GucE";
+const CID_V1: &[u8] = b"bafkreig73zl2zl2iyiurn6dyobqou7vw2eughkg2d6plzxwhmtsemzcvqm";
+
+#[test]
+fn test_parse_cid() {
+	let digest = sp_core::hashing::sha2_256(b"metamui document").to_vec();
+
+	let mut padded = [0u8; 64];
+	padded[..CID_V0.len()].copy_from_slice(CID_V0);
+	assert_eq!(
+		cid::parse_cid(&padded),
+		Ok(cid::Cid { version: 0, codec: cid::DAG_PB, hash_code: cid::SHA2_256, digest: digest.clone() })
+	);
+
+	// raw binary content
+	let v1 = cid::Cid { version: 1, codec: 0x55, hash_code: cid::SHA2_256, digest: digest.clone() };
+	assert_eq!(cid::parse_cid(CID_V1), Ok(v1.clone()));
+	assert_eq!(cid::parse_cid(&CID_V1.to_ascii_uppercase()), Ok(v1.clone()));
+
+	let mut base16 = b"f01551220".to_vec();
+	base16.extend(digest.iter().flat_map(|byte| format!("{:02x}", byte).into_bytes()));
+	assert_eq!(cid::parse_cid(&base16), Ok(v1));
+}
+
+#[test]
+fn test_parse_cid_errors() {
+	use cid::{parse_cid, CidError};
+
+	assert_eq!(parse_cid(&[0; 64]), Err(CidError::Empty));
+	assert_eq!(parse_cid(b"F0TAeD_UY2mK-agbzZTW"), Err(CidError::InvalidCharacter));
+	assert_eq!(parse_cid(b"xabc"), Err(CidError::UnsupportedBase));
+	assert_eq!(parse_cid(b"b0"), Err(CidError::InvalidCharacter));
+	assert_eq!(parse_cid(&CID_V1[..49]), Err(CidError::InvalidDigestLength));
+	// a character left over without a full byte
+	assert_eq!(parse_cid(&CID_V1[..50]), Err(CidError::NonCanonicalEncoding));
+	// non zero trailing bits
+	let mut cid = CID_V1.to_vec();
+	*cid.last_mut().unwrap() = b'n';
+	assert_eq!(parse_cid(&cid), Err(CidError::NonCanonicalEncoding));
+	// sha2-512, its multihash does not fit the stored CID
+	let mut sha2_512 = b"f01551340".to_vec();
+	sha2_512.extend([b'0'; 128]);
+	assert_eq!(parse_cid(&sha2_512), Err(CidError::UnsupportedHash));
+	// version 2
+	assert_eq!(parse_cid(b"f0255"), Err(CidError::UnsupportedVersion));
+	// identity hash
+	assert_eq!(parse_cid(b"f0155000100"), Err(CidError::UnsupportedHash));
+	// truncated varint
+	assert_eq!(parse_cid(b"f0180"), Err(CidError::InvalidVarint));
+}
@@ -0,0 +1,21 @@
+//! Decode arbitrary bytes as a VC, as a VC envelope and as each of the VC properties
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use metamui_primitives::types::{
+	PrivateDidVC, PublicDidVC, SlashMintTokens, TokenTransferVC, TokenVC, VC as VCStruct,
+};
+use pallet_vc_fuzz::mock::*;
+use sp_core::H256;
+
+fuzz_target!(|data: &[u8]| {
+	new_test_ext().execute_with(|| {
+		let _ = VC::decode_vc::<VCStruct<H256>>(data);
+		let _ = VC::decode_versioned_vc(data);
+		let _ = VC::decode_vc::<TokenVC>(data);
+		let _ = VC::decode_vc::<SlashMintTokens>(data);
+		let _ = VC::decode_vc::<TokenTransferVC>(data);
+		let _ = VC::decode_vc::<PublicDidVC>(data);
+		let _ = VC::decode_vc::<PrivateDidVC>(data);
+	});
+});
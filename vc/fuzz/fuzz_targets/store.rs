@@ -0,0 +1,11 @@
+//! Store arbitrary bytes as a VC, from a caller allowed to store every type of VC
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pallet_vc_fuzz::mock::*;
+
+fuzz_target!(|data: &[u8]| {
+	new_test_ext().execute_with(|| {
+		let _ = VC::store(Origin::signed(BOB_ACCOUNT_ID), data.to_vec());
+	});
+});
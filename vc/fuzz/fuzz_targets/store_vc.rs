@@ -0,0 +1,61 @@
+//! Store well formed VCs with arbitrary properties, so the checks after decoding are reached
+#![no_main]
+
+use arbitrary::Arbitrary;
+use codec::Encode;
+use libfuzzer_sys::fuzz_target;
+use metamui_primitives::{types::VCType, Did};
+use pallet_vc_fuzz::mock::*;
+use sp_core::{sr25519, Pair};
+use sp_runtime::traits::BlakeTwo256;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+	vc_type: u8,
+	vc_property: Vec<u8>,
+	owner: u8,
+	issuers: Vec<u8>,
+	max_uses: Option<u32>,
+	signed_by_bob: bool,
+}
+
+const VC_TYPES: [VCType; 7] = [
+	VCType::TokenVC,
+	VCType::SlashTokens,
+	VCType::MintTokens,
+	VCType::TokenTransferVC,
+	VCType::GenericVC,
+	VCType::PublicDidVC,
+	VCType::PrivateDidVC,
+];
+
+/// One of the dids of the mock runtime, or an unknown one
+fn did(index: u8) -> Did {
+	match index % 5 {
+		0 => ALICE,
+		1 => BOB,
+		2 => DAVE,
+		3 => EVE,
+		_ => [index; 32],
+	}
+}
+
+fuzz_target!(|input: Input| {
+	let mut vc_property = input.vc_property;
+	vc_property.resize(128, 0);
+	let mut vc = metamui_vc_verifier::build_vc::<BlakeTwo256>(
+		VC_TYPES[input.vc_type as usize % VC_TYPES.len()].clone(),
+		convert_to_array::<128>(vc_property),
+		did(input.owner),
+		input.issuers.into_iter().take(8).map(did).collect(),
+		input.max_uses,
+	);
+	if input.signed_by_bob {
+		let bob_pair = sr25519::Pair::from_seed(&BOB_SEED);
+		vc.signatures.push(bob_pair.sign(vc.hash.as_ref()).into());
+	}
+
+	new_test_ext().execute_with(|| {
+		let _ = VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode());
+	});
+});
@@ -0,0 +1,8 @@
+//! Mock runtime of pallet-vc shared by the fuzz targets
+//!
+//! The mock of the pallet tests is reused as is, it refers to the pallet through `crate`.
+
+pub use pallet_vc::*;
+
+#[path = "../../src/mock.rs"]
+pub mod mock;
@@ -0,0 +1,250 @@
+//! RPC interface for pallet-vc
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use metamui_primitives::{types::VC, Did, VCid};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, MultiSignature};
+
+pub use vc_runtime_api::{Cid, PresentationVerdict, VCApi as VCRuntimeApi, VCStatus};
+
+/// Number of VCs returned in a page when no page size is given
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+/// Maximum number of VCs returned in a page
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// A VC along with its id
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VCRecord<Hash> {
+	/// Id of the VC
+	pub vc_id: VCid,
+	/// The VC
+	pub vc: VC<Hash>,
+}
+
+/// A page of results
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+	/// Entries of the page
+	pub items: Vec<T>,
+	/// Index of the page, starting at 0
+	pub page: u32,
+	/// Index of the next page, `None` if this is the last page
+	pub next_page: Option<u32>,
+}
+
+#[rpc(client, server)]
+pub trait VCApi<BlockHash, Hash> {
+	/// VCs currently owned by the Did
+	#[method(name = "vc_vcsOfOwner")]
+	fn vcs_of_owner(
+		&self,
+		did: Did,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<VCRecord<Hash>>>;
+
+	/// VCs issued by the Did
+	#[method(name = "vc_vcsIssuedBy")]
+	fn vcs_issued_by(
+		&self,
+		did: Did,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Page<VCRecord<Hash>>>;
+
+	/// Current status of the VC
+	#[method(name = "vc_status")]
+	fn vc_status(&self, vc_id: VCid, at: Option<BlockHash>) -> RpcResult<Option<VCStatus>>;
+
+	/// Issuers of the VC who have not signed it yet
+	#[method(name = "vc_pendingSignatures")]
+	fn pending_signatures(&self, vc_id: VCid, at: Option<BlockHash>) -> RpcResult<Vec<Did>>;
+
+	/// Verify that the challenge is signed by the current owner of the VC
+	///
+	/// The wallet signs the SCALE encoding of `(b"metamui/vc-presentation", vc_id, challenge)`:
+	/// the 23 context bytes, the 32 bytes of the VC id, then the compact length of the challenge
+	/// and the challenge itself.
+	#[method(name = "vc_verifyPresentation")]
+	fn verify_presentation(
+		&self,
+		vc_id: VCid,
+		challenge: Bytes,
+		owner_signature: MultiSignature,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<PresentationVerdict>>;
+
+	/// CID of the document referenced by a GenericVC, with the digest of the document
+	#[method(name = "vc_documentCid")]
+	fn document_cid(&self, vc_id: VCid, at: Option<BlockHash>) -> RpcResult<Option<Cid>>;
+}
+
+/// Provides RPC methods to query the VCs stored on chain
+pub struct VerifiedCredentials<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> VerifiedCredentials<C, P> {
+	/// Create new `VerifiedCredentials` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested page size is not allowed.
+	InvalidPageSize,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidPageSize => 2,
+		}
+	}
+}
+
+fn runtime_error(e: impl std::fmt::Display) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query VCs.",
+		Some(e.to_string()),
+	))
+	.into()
+}
+
+/// Offset and page size of the requested page
+fn page_bounds(page: Option<u32>, page_size: Option<u32>) -> RpcResult<(u32, u32)> {
+	let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+	if page_size == 0 || page_size > MAX_PAGE_SIZE {
+		return Err(CallError::Custom(ErrorObject::owned(
+			Error::InvalidPageSize.into(),
+			"Page size must be between 1 and the maximum page size.",
+			Some(MAX_PAGE_SIZE),
+		))
+		.into())
+	}
+	let offset = page.unwrap_or(0).saturating_mul(page_size);
+	Ok((offset, page_size))
+}
+
+/// Build the page from the entries, one entry more than the page size is fetched to know if
+/// there is a next page
+fn to_page<Hash>(
+	mut vcs: Vec<(VCid, VC<Hash>)>,
+	page: Option<u32>,
+	page_size: u32,
+) -> Page<VCRecord<Hash>> {
+	let page = page.unwrap_or(0);
+	let has_next = vcs.len() > page_size as usize;
+	vcs.truncate(page_size as usize);
+	Page {
+		items: vcs.into_iter().map(|(vc_id, vc)| VCRecord { vc_id, vc }).collect(),
+		page,
+		next_page: if has_next { page.checked_add(1) } else { None },
+	}
+}
+
+#[async_trait]
+impl<C, Block, Hash> VCApiServer<<Block as BlockT>::Hash, Hash> for VerifiedCredentials<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: VCRuntimeApi<Block, Hash>,
+	Hash: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+	fn vcs_of_owner(
+		&self,
+		did: Did,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Page<VCRecord<Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let (offset, page_size) = page_bounds(page, page_size)?;
+
+		let vcs = api.vcs_of_owner(&at, did, offset, page_size + 1).map_err(runtime_error)?;
+		Ok(to_page(vcs, page, page_size))
+	}
+
+	fn vcs_issued_by(
+		&self,
+		did: Did,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Page<VCRecord<Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+		let (offset, page_size) = page_bounds(page, page_size)?;
+
+		let vcs = api.vcs_issued_by(&at, did, offset, page_size + 1).map_err(runtime_error)?;
+		Ok(to_page(vcs, page, page_size))
+	}
+
+	fn vc_status(
+		&self,
+		vc_id: VCid,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<VCStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.vc_status(&at, vc_id).map_err(runtime_error)
+	}
+
+	fn pending_signatures(
+		&self,
+		vc_id: VCid,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<Did>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.pending_signatures(&at, vc_id).map_err(runtime_error)
+	}
+
+	fn verify_presentation(
+		&self,
+		vc_id: VCid,
+		challenge: Bytes,
+		owner_signature: MultiSignature,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<PresentationVerdict>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.verify_presentation(&at, vc_id, challenge.to_vec(), owner_signature)
+			.map_err(runtime_error)
+	}
+
+	fn document_cid(
+		&self,
+		vc_id: VCid,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Cid>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.document_cid(&at, vc_id).map_err(runtime_error)
+	}
+}
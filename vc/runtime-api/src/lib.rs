@@ -0,0 +1,34 @@
+//! Runtime API definition for pallet-vc
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use metamui_primitives::{types::VC, Did, VCid};
+pub use pallet_vc::{Cid, PresentationVerdict, VCStatus};
+use sp_runtime::MultiSignature;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// API to query the VCs stored on chain
+	pub trait VCApi<Hash> where Hash: Codec {
+		/// VCs currently owned by the Did, at most `limit` of them starting at `offset`
+		fn vcs_of_owner(did: Did, offset: u32, limit: u32) -> Vec<(VCid, VC<Hash>)>;
+		/// VCs issued by the Did, at most `limit` of them starting at `offset`
+		fn vcs_issued_by(did: Did, offset: u32, limit: u32) -> Vec<(VCid, VC<Hash>)>;
+		/// Current status of the VC, `None` if it does not exist
+		fn vc_status(vc_id: VCid) -> Option<VCStatus>;
+		/// Issuers of the VC who have not signed it yet
+		fn pending_signatures(vc_id: VCid) -> Vec<Did>;
+		/// Verify that the challenge is signed by the current owner of the VC, `None` if the VC
+		/// does not exist
+		///
+		/// The owner signs the SCALE encoding of `(b"metamui/vc-presentation", vc_id, challenge)`,
+		/// not the bare challenge.
+		fn verify_presentation(
+			vc_id: VCid,
+			challenge: Vec<u8>,
+			owner_signature: MultiSignature,
+		) -> Option<PresentationVerdict>;
+		/// CID of the document referenced by a GenericVC, `None` for other VCs
+		fn document_cid(vc_id: VCid) -> Option<Cid>;
+	}
+}
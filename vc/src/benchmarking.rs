@@ -0,0 +1,380 @@
+//! Benchmarking setup for pallet-vc
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as VCPallet;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::{traits::UnfilteredDispatchable, BoundedVec};
+use frame_system::RawOrigin;
+use sp_core::{crypto::KeyTypeId, sr25519};
+use sp_runtime::traits::Bounded;
+
+/// Key type of the issuer keys generated in the keystore
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"vcbm");
+
+/// Did made of the prefix followed by the index
+fn did(prefix: &[u8], index: u32) -> Did {
+  let mut did = [0u8; 32];
+  did[..prefix.len()].copy_from_slice(prefix);
+  did[prefix.len()..prefix.len() + 4].copy_from_slice(&index.to_be_bytes());
+  did
+}
+
+/// Register a did with a new sr25519 key from the keystore
+fn create_did<T: Config>(did: Did) -> sr25519::Public {
+  let public_key = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+  T::DidResolution::create_private_did(did, PublicKey::Sr25519(public_key));
+  public_key
+}
+
+/// Register `count` issuer dids
+fn create_issuers<T: Config>(count: u32) -> Vec<(Did, sr25519::Public)> {
+  (0..count).map(|i| {
+    let issuer = did(b"did:ssid:issuer", i);
+    (issuer, create_did::<T>(issuer))
+  }).collect()
+}
+
+fn sign(public_key: &sr25519::Public, hash: &[u8]) -> MultiSignature {
+  sp_io::crypto::sr25519_sign(KEY_TYPE, public_key, hash)
+    .expect("key is in the keystore")
+    .into()
+}
+
+/// Property of a VC holding the encoded value
+fn vc_property(value: impl Encode) -> [u8; 128] {
+  let mut vc_property = [0u8; 128];
+  let encoded = value.encode();
+  vc_property[..encoded.len()].copy_from_slice(&encoded);
+  vc_property
+}
+
+/// Property of the benchmark TokenVC, the reservable balance tells its versions apart
+fn token_property(reservable_balance: u128) -> [u8; 128] {
+  vc_property(TokenVC {
+    token_name: *b"benchmark token\0",
+    reservable_balance,
+    decimal: 6,
+    currency_code: *b"OTH\0\0\0\0\0",
+    max_supply: None,
+  })
+}
+
+/// VC owned by `owner`, signed by the first `signers` issuers
+fn signed_vc<T: Config>(
+  vc_type: VCType,
+  vc_property: [u8; 128],
+  owner: Did,
+  issuers: &[(Did, sr25519::Public)],
+  signers: u32,
+) -> VC<T::Hash> {
+  let mut vc = metamui_vc_verifier::build_vc::<T::Hashing>(
+    vc_type,
+    vc_property,
+    owner,
+    issuers.iter().map(|(issuer, _)| *issuer).collect(),
+    Some(1),
+  );
+  for (_, public_key) in issuers.iter().take(signers as usize) {
+    let signature = sign(public_key, vc.hash.as_ref());
+    vc.signatures.push(signature);
+  }
+  vc
+}
+
+/// Register the did the benchmark VCs are owned by
+fn create_owner<T: Config>() -> Did {
+  let owner = did(b"did:ssid:owner", 0);
+  create_did::<T>(owner);
+  owner
+}
+
+/// Token VC owned by a new did, signed by the first `signers` issuers
+fn token_vc<T: Config>(issuers: &[(Did, sr25519::Public)], signers: u32) -> VC<T::Hash> {
+  let owner = create_owner::<T>();
+  signed_vc::<T>(VCType::TokenVC, token_property(1000), owner, issuers, signers)
+}
+
+fn vc_id<T: Config>(vc: &VC<T::Hash>) -> VCid {
+  *BlakeTwo256::hash_of(vc).as_fixed_bytes()
+}
+
+/// Store a VC through the approve origin
+fn store_approved<T: Config>(vc: &VC<T::Hash>) -> Result<(), DispatchError> {
+  VCPallet::<T>::store(T::ApproveOrigin::successful_origin(), vc.encode())
+    .map_err(|e| e.error)?;
+  Ok(())
+}
+
+/// Account of a registered did, funded to pay the storage deposits
+fn funded_account<T: Config>(did: &Did) -> T::AccountId {
+  let account = T::DidResolution::get_account_id(did).expect("did is registered");
+  T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value() / 2u32.into());
+  account
+}
+
+/// Fill the prune queues of the current block and the next ones, so the next retired VC is
+/// queued in the last block searched
+fn fill_prune_queues<T: Config>() {
+  let full: BoundedVec<VCid, T::MaxRetiredPerBlock> =
+    vec![[0u8; 32]; T::MaxRetiredPerBlock::get() as usize]
+      .try_into()
+      .expect("the queue holds MaxRetiredPerBlock VCs");
+  let now = frame_system::Pallet::<T>::block_number();
+  for offset in 0..MAX_RETIRE_SCAN - 1 {
+    PruneQueue::<T>::insert(now + offset.into(), full.clone());
+  }
+}
+
+/// Store a token VC signed by its `i` issuers, returns its id and the account of its owner
+fn stored_token_vc<T: Config>(i: u32) -> Result<(VCid, T::AccountId), DispatchError> {
+  let issuers = create_issuers::<T>(i);
+  let vc = token_vc::<T>(&issuers, i);
+  let vc_id = vc_id::<T>(&vc);
+  store_approved::<T>(&vc)?;
+  let owner = T::DidResolution::get_account_id(&vc.owner).expect("owner did is registered");
+  Ok((vc_id, owner))
+}
+
+benchmarks! {
+  store {
+    let i in 1 .. T::MaxIssuers::get();
+    let s in 0 .. T::MaxIssuers::get();
+    // every issuer signs at most once
+    let s = s.min(i);
+
+    let issuers = create_issuers::<T>(i);
+    let vc = token_vc::<T>(&issuers, s);
+    let vc_id = vc_id::<T>(&vc);
+    let origin = T::ApproveOrigin::successful_origin();
+    let call = Call::<T>::store { vc_hex: vc.encode() };
+  }: { call.dispatch_bypass_filter(origin)? }
+  verify {
+    assert_eq!(VCs::<T>::get(vc_id).map(|vc| vc.is_vc_active), Some(s == i));
+  }
+
+  store_generic_vc {
+    let i in 1 .. T::MaxIssuers::get();
+    let s in 0 .. T::MaxIssuers::get();
+    let s = s.min(i);
+
+    // a council member stores the VC and pays its deposit
+    let issuers = create_issuers::<T>(i);
+    let owner = create_owner::<T>();
+    let member = did(b"did:ssid:council", 0);
+    create_did::<T>(member);
+    T::IsCouncilMember::add_collective_member(&member);
+    let caller = funded_account::<T>(&member);
+
+    let mut cid = [0u8; 64];
+    cid[..46].copy_from_slice(b"QmdQYGSHZDGk6jDwCP2Sy4ybFAdH5PUvbt1oCcZzS1GucE");
+    let vc = signed_vc::<T>(VCType::GenericVC, vc_property(GenericVC { cid }), owner, &issuers, s);
+    let vc_id = vc_id::<T>(&vc);
+  }: store(RawOrigin::Signed(caller), vc.encode())
+  verify {
+    assert_eq!(VCs::<T>::get(vc_id).map(|vc| vc.is_vc_active), Some(s == i));
+  }
+
+  store_linked_vc {
+    let i in 1 .. T::MaxIssuers::get();
+    let s in 0 .. T::MaxIssuers::get();
+    let s = s.min(i);
+
+    // the first issuer owns the TokenVC, which has been superseded the most times
+    let issuers = create_issuers::<T>(i);
+    let (token_owner, _) = issuers[0];
+    let token_vc = signed_vc::<T>(
+      VCType::TokenVC,
+      token_property(1000),
+      token_owner,
+      &issuers[..1],
+      1,
+    );
+    let token_vc_id = vc_id::<T>(&token_vc);
+    store_approved::<T>(&token_vc)?;
+    let mut first_vc_id = token_vc_id;
+    for version in (0..T::MaxSupersedeDepth::get()).rev() {
+      let old_vc_id = did(b"vc:superseded", version);
+      SupersededBy::<T>::insert(old_vc_id, first_vc_id);
+      first_vc_id = old_vc_id;
+    }
+
+    // the token owner stores the VC and pays its deposit
+    let caller = funded_account::<T>(&token_owner);
+    let owner = create_owner::<T>();
+    let mint_tokens = SlashMintTokens {
+      vc_id: first_vc_id,
+      currency_code: *b"OTH\0\0\0\0\0",
+      amount: 1000,
+    };
+    let vc = signed_vc::<T>(VCType::MintTokens, vc_property(mint_tokens), owner, &issuers, s);
+    let vc_id = vc_id::<T>(&vc);
+  }: store(RawOrigin::Signed(caller), vc.encode())
+  verify {
+    assert_eq!(VCs::<T>::get(vc_id).map(|vc| vc.is_vc_active), Some(s == i));
+  }
+
+  supersede {
+    let i in 1 .. T::MaxIssuers::get();
+    let s in 0 .. T::MaxIssuers::get();
+    let s = s.min(i);
+
+    // once fully signed the new version deactivates the old one, which is retired in the last
+    // block of the prune queue searched
+    let issuers = create_issuers::<T>(i);
+    let old_vc = token_vc::<T>(&issuers, i);
+    let old_vc_id = vc_id::<T>(&old_vc);
+    store_approved::<T>(&old_vc)?;
+    fill_prune_queues::<T>();
+
+    let vc = signed_vc::<T>(VCType::TokenVC, token_property(2000), old_vc.owner, &issuers, s);
+    let vc_id = vc_id::<T>(&vc);
+    let origin = T::ApproveOrigin::successful_origin();
+    let call = Call::<T>::supersede { old_vc_id, vc_hex: vc.encode() };
+  }: { call.dispatch_bypass_filter(origin)? }
+  verify {
+    if s == i {
+      assert_eq!(SupersededBy::<T>::get(old_vc_id), Some(vc_id));
+    } else {
+      assert_eq!(PendingSupersede::<T>::get(old_vc_id), Some(vc_id));
+    }
+  }
+
+  add_signature {
+    let i in 1 .. T::MaxIssuers::get();
+
+    // every issuer but the last has signed
+    let issuers = create_issuers::<T>(i);
+    let vc = token_vc::<T>(&issuers, i - 1);
+    let vc_id = vc_id::<T>(&vc);
+    store_approved::<T>(&vc)?;
+
+    let (last_issuer, public_key) = issuers[i as usize - 1];
+    let signature = sign(&public_key, vc.hash.as_ref());
+    let caller = T::DidResolution::get_account_id(&last_issuer).expect("issuer did is registered");
+  }: _(RawOrigin::Signed(caller), vc_id, signature)
+  verify {
+    assert_eq!(VCs::<T>::get(vc_id).map(|vc| vc.is_vc_active), Some(true));
+  }
+
+  add_signatures {
+    let n in 1 .. T::MaxSignaturesPerBatch::get();
+    let i in 1 .. T::MaxIssuers::get();
+
+    // every issuer but the last has signed each VC
+    let issuers = create_issuers::<T>(i);
+    let owner = create_owner::<T>();
+    let (last_issuer, public_key) = issuers[i as usize - 1];
+    let mut signatures = Vec::new();
+    for version in 0..n {
+      let vc = signed_vc::<T>(
+        VCType::TokenVC,
+        token_property(version as u128),
+        owner,
+        &issuers,
+        i - 1,
+      );
+      store_approved::<T>(&vc)?;
+      signatures.push((vc_id::<T>(&vc), sign(&public_key, vc.hash.as_ref())));
+    }
+    let vc_ids: Vec<VCid> = signatures.iter().map(|(vc_id, _)| *vc_id).collect();
+    let signatures: BoundedVec<_, T::MaxSignaturesPerBatch> =
+      signatures.try_into().expect("the batch holds MaxSignaturesPerBatch signatures");
+    let caller = T::DidResolution::get_account_id(&last_issuer).expect("issuer did is registered");
+  }: _(RawOrigin::Signed(caller), signatures)
+  verify {
+    for vc_id in vc_ids {
+      assert_eq!(VCs::<T>::get(vc_id).map(|vc| vc.is_vc_active), Some(true));
+    }
+  }
+
+  update_status {
+    // activating the new version of a VC deactivates the old one, which is retired in the last
+    // block of the prune queue searched
+    let issuers = create_issuers::<T>(1);
+    let old_vc = token_vc::<T>(&issuers, 1);
+    let old_vc_id = vc_id::<T>(&old_vc);
+    store_approved::<T>(&old_vc)?;
+    let vc = signed_vc::<T>(VCType::TokenVC, token_property(2000), old_vc.owner, &issuers, 0);
+    let vc_id = vc_id::<T>(&vc);
+    VCPallet::<T>::supersede(T::ApproveOrigin::successful_origin(), old_vc_id, vc.encode())
+      .map_err(|e| e.error)?;
+    fill_prune_queues::<T>();
+
+    let caller = T::DidResolution::get_account_id(&issuers[0].0).expect("issuer did is registered");
+  }: _(RawOrigin::Signed(caller), vc_id, true)
+  verify {
+    assert_eq!(SupersededBy::<T>::get(old_vc_id), Some(vc_id));
+    let last_block = frame_system::Pallet::<T>::block_number() + (MAX_RETIRE_SCAN - 1).into();
+    assert_eq!(RetiredAt::<T>::get(old_vc_id), Some(last_block));
+  }
+
+  request_transfer {
+    let (vc_id, owner) = stored_token_vc::<T>(1)?;
+    let new_owner = did(b"did:ssid:new_owner", 0);
+    create_did::<T>(new_owner);
+  }: _(RawOrigin::Signed(owner), vc_id, new_owner)
+  verify {
+    assert!(PendingTransfers::<T>::contains_key(vc_id));
+  }
+
+  approve_transfer {
+    let i in 1 .. T::MaxIssuers::get();
+
+    // every issuer but the last has consented
+    let (vc_id, _) = stored_token_vc::<T>(i)?;
+    let issuers = VCs::<T>::get(vc_id).expect("VC is stored").issuers;
+    let new_owner = did(b"did:ssid:new_owner", 0);
+    create_did::<T>(new_owner);
+    let approvals = issuers[..i as usize - 1].to_vec();
+    PendingTransfers::<T>::insert(vc_id, VCTransfer { new_owner, approvals });
+
+    let last_issuer = issuers[i as usize - 1];
+    let caller = T::DidResolution::get_account_id(&last_issuer).expect("issuer did is registered");
+  }: _(RawOrigin::Signed(caller), vc_id)
+  verify {
+    assert_eq!(VCPallet::<T>::vc_owner(&vc_id), Some(new_owner));
+  }
+
+  force_approve_transfer {
+    let (vc_id, _) = stored_token_vc::<T>(1)?;
+    let new_owner = did(b"did:ssid:new_owner", 0);
+    create_did::<T>(new_owner);
+    PendingTransfers::<T>::insert(vc_id, VCTransfer { new_owner, approvals: vec![] });
+    let origin = T::TransferOrigin::successful_origin();
+    let call = Call::<T>::force_approve_transfer { vc_id };
+  }: { call.dispatch_bypass_filter(origin)? }
+  verify {
+    assert_eq!(VCPallet::<T>::vc_owner(&vc_id), Some(new_owner));
+  }
+
+  cancel_transfer {
+    let (vc_id, owner) = stored_token_vc::<T>(1)?;
+    let new_owner = did(b"did:ssid:new_owner", 0);
+    create_did::<T>(new_owner);
+    PendingTransfers::<T>::insert(vc_id, VCTransfer { new_owner, approvals: vec![] });
+  }: _(RawOrigin::Signed(owner), vc_id)
+  verify {
+    assert!(!PendingTransfers::<T>::contains_key(vc_id));
+  }
+
+  prune_vc {
+    let i in 1 .. T::MaxIssuers::get();
+
+    // the revoked VC holds a deposit and is due to be pruned
+    let (vc_id, owner) = stored_token_vc::<T>(i)?;
+    let len = VCs::<T>::get(vc_id).expect("VC is stored").encoded_size();
+    T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value() / 2u32.into());
+    VCPallet::<T>::reserve_deposit(vc_id, owner, len)?;
+    VCPallet::<T>::update_vc_status(vc_id, false)?;
+    let retired_at = RetiredAt::<T>::get(vc_id).expect("VC is retired");
+    PruneCursor::<T>::put(retired_at);
+    let now = retired_at + T::RetentionPeriod::get();
+  }: { VCPallet::<T>::prune_vcs(now, Weight::max_value()) }
+  verify {
+    assert!(PrunedVCs::<T>::contains_key(vc_id));
+  }
+}
+
+impl_benchmark_test_suite!(VCPallet, crate::mock::new_test_ext(), crate::mock::Test);
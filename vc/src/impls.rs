@@ -1,26 +1,50 @@
 use super::pallet::*;
 use codec::{ Decode };
 use sp_runtime::{ DispatchError };
-use metamui_primitives::{ VCid, traits::VCResolve, types::VC };
+use metamui_primitives::{ Did, VCid, traits::{ DidVCProperty, VCResolve }, types::{ VCType, VC } };
+use sp_std::prelude::*;
 
+/// VCs are resolved to their latest version, so references to a replaced VC keep working
 impl<T: Config> VCResolve<T::Hash> for Pallet<T> {
   /// Decoding VC from encoded bytes
   fn decode_vc<E: Decode>(vc_bytes: &[u8]) -> Result<E, DispatchError> {
     Self::decode_vc::<E>(vc_bytes)
   }   
 
+  fn decode_did_vc<E: DidVCProperty>(vc_id: &VCid) -> Result<E, DispatchError> {
+    Self::decode_did_vc::<E>(&Self::latest_vc_id(vc_id))
+  }
+
   fn get_vc(vc_id: &VCid) -> Option<VC<T::Hash>> {
-    VCs::<T>::get(vc_id)
+    Self::normalized_vc(&Self::latest_vc_id(vc_id))
+  }
+
+  fn latest_vc_id(vc_id: &VCid) -> VCid {
+    Self::latest_vc_id(vc_id)
+  }
+
+  fn get_vc_owner(vc_id: &VCid) -> Option<Did> {
+    Self::vc_owner(&Self::latest_vc_id(vc_id))
   }
 
   fn is_vc_used(vc_id: &VCid) -> bool {
+    let vc_id = Self::latest_vc_id(vc_id);
     match VCs::<T>::get(vc_id) {
-      Some(vc) => vc.is_vc_used,
-      None => false
+      Some(vc) => vc.is_vc_used(),
+      // only used or revoked VCs are pruned
+      None => PrunedVCs::<T>::contains_key(vc_id)
     }
   }
 
-  fn set_is_vc_used(vc_id: &VCid, is_vc_used: bool) {
-    Self::set_is_used_flag(*vc_id, Some(is_vc_used));
+  fn consume_vc(vc_id: &VCid) -> Result<Option<u32>, DispatchError> {
+    Self::consume(Self::latest_vc_id(vc_id))
+  }
+
+  fn consume_vc_amount(vc_id: &VCid, amount: u128) -> Result<u128, DispatchError> {
+    Self::consume_amount(Self::latest_vc_id(vc_id), amount)
+  }
+
+  fn vcs_of_type(vc_type: VCType) -> Vec<(VCid, VC<T::Hash>)> {
+    VCs::<T>::iter().filter(|(_, vc)| vc.vc_type == vc_type).collect()
   }
 }
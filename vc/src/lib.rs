@@ -3,41 +3,60 @@
 use frame_support::{
   codec::{ Decode, Encode },
   ensure, fail,
-  traits::EnsureOrigin
+  traits::{ Currency, EnsureOrigin, Get, ReservableCurrency },
+  weights::Weight,
 };
 
 use frame_system::{self, ensure_signed};
-use sp_core::sr25519;
 use sp_runtime::{
-  traits::{ BlakeTwo256, Hash, Verify },
-  DispatchError,
+  traits::{ BlakeTwo256, CheckedSub, Hash, One, Saturating, Zero },
+  DispatchError, MultiSignature,
 };
 use metamui_primitives::{ 
   Did, VCid, VCHex,
-  traits::{ DidResolve, IsMember, MultiAddress, IsValidator },
-  types::{ VCType, VC, TokenVC, SlashMintTokens, TokenTransferVC, PublicDidVC, PrivateDidVC }
+  traits::{ DidResolve, DidVCProperty, IsMember, MultiAddress, IsValidator, VCLinks },
+  types::{ VCType, VC, VCExtension, VersionedVC, TokenVC, SlashMintTokens, TokenTransferVC, PublicDidVC, PrivateDidVC, PublicKey }
 };
+use metamui_vc_verifier::VerifyError;
+pub use metamui_vc_verifier::cid::Cid;
 use sp_std::{ prelude::*, vec };
-use sr25519::Signature;
 
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 mod impls;
 
+pub mod migrations;
 pub mod types;
+pub mod weights;
 pub use crate::types::*;
+pub use weights::WeightInfo;
 use serde_big_array::big_array;
 
+/// Number of blocks of the prune queue searched for room when retiring a VC
+pub const MAX_RETIRE_SCAN: u32 = 16;
+
+/// Context prefixed to a presentation challenge before the owner signs it
+pub const PRESENTATION_CONTEXT: [u8; 23] = *b"metamui/vc-presentation";
+
+type BalanceOf<T> =
+  <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
 pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
-  use frame_support::pallet_prelude::*;
+  use frame_support::{pallet_prelude::*, traits::GetStorageVersion};
   use frame_system::pallet_prelude::*;
 
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+
   /// Configure the pallet by specifying the parameters and types on which it depends.
   #[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -53,6 +72,44 @@ pub mod pallet {
 
     /// Resolve Did from account Id
     type DidResolution: DidResolve<Self::AccountId>;
+
+    /// Origin which can approve a VC transfer in place of the issuers
+    type TransferOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+    /// Number of blocks a used or revoked VC is kept before it is pruned
+    type RetentionPeriod: Get<Self::BlockNumber>;
+
+    /// Maximum number of VCs pruned in a block, each empty block of the prune queue visited
+    /// counts as a VC
+    type MaxPrunedPerBlock: Get<u32>;
+
+    /// Maximum number of VCs in the prune queue of a block, further VCs are queued in the next
+    /// blocks
+    type MaxRetiredPerBlock: Get<u32>;
+
+    /// Maximum number of times a VC can be replaced by a newer version
+    type MaxSupersedeDepth: Get<u32>;
+
+    /// Maximum number of issuers of a VC
+    type MaxIssuers: Get<u32>;
+
+    /// Maximum number of signatures added in one batch
+    type MaxSignaturesPerBatch: Get<u32>;
+
+    /// Currency in which the VC storage deposits are reserved
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// Deposit reserved for storing a VC, on top of the deposit per byte
+    type VCDepositBase: Get<BalanceOf<Self>>;
+
+    /// Deposit reserved per byte of the stored VC
+    type VCDepositPerByte: Get<BalanceOf<Self>>;
+
+    /// Pallets depending on stored VCs, a VC linked by them is not pruned
+    type VCLinks: VCLinks<Self::Hash>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
@@ -62,6 +119,28 @@ pub mod pallet {
 		VCValidated{ vcid: VCid },
 		/// Updated VC status flag
 		VCStatusUpdated{ vcid: VCid, vcstatus: IsVCActive },
+		/// One use of the VC is consumed
+		VCConsumed{ vcid: VCid, remaining_uses: Option<u32> },
+		/// Part of the amount authorised by the VC is consumed
+		VCAmountConsumed{ vcid: VCid, amount: u128, remaining_amount: u128 },
+		/// The owner requested to transfer the VC to another Did
+		VCTransferRequested{ vcid: VCid, from: Did, to: Did },
+		/// An issuer consented to the pending transfer of the VC
+		VCTransferApproved{ vcid: VCid, issuer: Did },
+		/// The owner cancelled the pending transfer of the VC
+		VCTransferCancelled{ vcid: VCid },
+		/// The VC is now owned by another Did
+		VCTransferred{ vcid: VCid, from: Did, to: Did },
+		/// The VC is replaced by a newer version and deactivated
+		VCSuperseded{ vcid: VCid, by: VCid },
+		/// The used or revoked VC is removed from storage
+		VCPruned{ vcid: VCid },
+		/// A batch of signatures is processed, failed signatures are skipped
+		SignaturesAdded{ signed: Vec<VCid>, failed: Vec<(VCid, DispatchError)> },
+		/// The storage deposit of the VC is reserved from the submitter
+		VCDepositReserved{ vcid: VCid, who: T::AccountId, amount: BalanceOf<T> },
+		/// The storage deposit of the VC is returned to the submitter
+		VCDepositReleased{ vcid: VCid, who: T::AccountId, amount: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -93,11 +172,50 @@ pub mod pallet {
     /// Did doesn't exist on chain
     DidDoesNotExist,
     /// Public key in the DidVC is already used
-    PublicKeyRegistered
+    PublicKeyRegistered,
+    /// VC has no uses left
+    VCAlreadyUsed,
+    /// The given type of VC does not carry an amount
+    VCHasNoAmount,
+    /// The amount must be greater than zero
+    InvalidAmount,
+    /// The amount is more than what is left in the VC
+    AmountExceedsRemaining,
+    /// The caller is not the owner of the VC
+    NotVCOwner,
+    /// The VC is already owned by the given Did
+    AlreadyVCOwner,
+    /// There is no pending transfer for the VC
+    NoPendingTransfer,
+    /// A transfer of the VC is already pending
+    TransferPending,
+    /// The caller is not an issuer of the VC
+    NotAnIssuer,
+    /// The VC is already replaced by a newer version
+    VCAlreadySuperseded,
+    /// The new version of a VC must have the same type
+    VCTypeMismatch,
+    /// The new version of a VC must be issued by every issuer of the replaced VC
+    MissingIssuers,
+    /// The VC has been replaced the maximum number of times
+    SupersedeChainTooLong,
+    /// A new version of the VC is already waiting for the signatures of its issuers
+    SupersedePending,
+    /// The VC has more issuers than allowed
+    TooManyIssuers,
+    /// The public key in the DidVC does not map to an account
+    InvalidPublicKey,
+    /// The CID in the GenericVC is not a valid CIDv0 or CIDv1
+    InvalidCid,
+    /// The VC has expired
+    VCExpired,
+    /// The prune queue has no room left in the next blocks
+    PruneQueueFull,
 	}
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 
 	pub struct Pallet<T>(_);
@@ -118,12 +236,106 @@ pub mod pallet {
 	#[pallet::storage]
   pub(super) type VCHistory<T: Config> = StorageMap<_, Blake2_128Concat, VCid, (IsVCActive, T::BlockNumber), OptionQuery>;
 
+	/// map to enable lookup from issuer Did to the VCids it issued
+	#[pallet::storage]
+  pub(super) type IssuerLookup<T: Config> = StorageMap<_, Blake2_128Concat, Did, Vec<VCid>, ValueQuery>;
+
 	/// map for vc id and approvers list
 	#[pallet::storage]
   pub(super) type VCApproverList<T: Config> = StorageMap<_, Blake2_128Concat, VCid, Vec<Did>, ValueQuery>;
 
+	/// map for the amount left in partially consumed token VCs
+	#[pallet::storage]
+  pub(super) type RemainingAmount<T: Config> = StorageMap<_, Blake2_128Concat, VCid, u128, OptionQuery>;
+
+	/// map from a VC to the newer version replacing it
+	#[pallet::storage]
+  pub(super) type SupersededBy<T: Config> = StorageMap<_, Blake2_128Concat, VCid, VCid, OptionQuery>;
+
+	/// map from a new version of a VC to the VC it replaces
+	#[pallet::storage]
+  pub(super) type Supersedes<T: Config> = StorageMap<_, Blake2_128Concat, VCid, VCid, OptionQuery>;
+
+	/// map from a new version of a VC to the number of versions before it
+	#[pallet::storage]
+  pub(super) type SupersedeDepth<T: Config> = StorageMap<_, Blake2_128Concat, VCid, u32, ValueQuery>;
+
+	/// map from a VC to its new version waiting for the signatures of its issuers
+	#[pallet::storage]
+  pub(super) type PendingSupersede<T: Config> = StorageMap<_, Blake2_128Concat, VCid, VCid, OptionQuery>;
+
+	/// map for the pending ownership transfers of VCs
+	#[pallet::storage]
+  pub(super) type PendingTransfers<T: Config> = StorageMap<_, Blake2_128Concat, VCid, VCTransfer, OptionQuery>;
+
+	/// map from a used or revoked VC to the block it was retired in
+	#[pallet::storage]
+  pub(super) type RetiredAt<T: Config> = StorageMap<_, Blake2_128Concat, VCid, T::BlockNumber, OptionQuery>;
+
+	/// map from a block to the VCs retired in it, in the order they are pruned
+	#[pallet::storage]
+  pub(super) type PruneQueue<T: Config> =
+    StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<VCid, T::MaxRetiredPerBlock>, ValueQuery>;
+
+	/// the next block of the prune queue to be processed
+	#[pallet::storage]
+  pub(super) type PruneCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// ids of the pruned VCs, so a pruned VC can not be stored again
+	#[pallet::storage]
+  pub(super) type PrunedVCs<T: Config> = StorageMap<_, Blake2_128Concat, VCid, (), OptionQuery>;
+
+	/// map for the extension of the VCs stored in a newer format than v1
+	#[pallet::storage]
+  pub(super) type VCExtensions<T: Config> = StorageMap<_, Blake2_128Concat, VCid, VCExtension, OptionQuery>;
+
+	/// DID VCs stored before multi-scheme keys, their property holds an sr25519 key
+	#[pallet::storage]
+  pub(super) type LegacyDidVCs<T: Config> = StorageMap<_, Blake2_128Concat, VCid, (), OptionQuery>;
+
+	/// GenericVCs stored before their hash covered the CID of the document, their hash is not
+	/// checked
+	#[pallet::storage]
+  pub(super) type LegacyGenericVCs<T: Config> = StorageMap<_, Blake2_128Concat, VCid, (), OptionQuery>;
+
+	/// map for the storage deposit of a VC and the account it is reserved from
+	#[pallet::storage]
+  pub(super) type VCDeposits<T: Config> = StorageMap<_, Blake2_128Concat, VCid, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	/// map for the key the owner's Did had when the VC was stored or transferred to it
+	#[pallet::storage]
+  pub(super) type OwnerKeys<T: Config> = StorageMap<_, Blake2_128Concat, VCid, PublicKey, OptionQuery>;
+
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			let mut weight = T::DbWeight::get().reads(1);
+			if on_chain_version < 1 {
+				weight += migrations::v1::migrate::<T>();
+			} else if on_chain_version < 2 {
+				weight += migrations::v2::migrate::<T>();
+			}
+			if on_chain_version < 3 {
+				weight += migrations::v3::migrate::<T>();
+			}
+			if on_chain_version < 4 {
+				weight += migrations::v4::migrate::<T>();
+			}
+			if on_chain_version < 5 {
+				weight += migrations::v5::migrate::<T>();
+			}
+			weight
+		}
+
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Pallet::<T>::prune_vcs(n, remaining_weight)
+		}
+
+		fn integrity_test() {
+			assert!(T::MaxRetiredPerBlock::get() > 0, "the prune queue of a block must have room");
+		}
+	}
 
   #[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -151,94 +363,213 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Adds a member to the membership set
-		#[pallet::weight(1)]
-		pub fn store(origin: OriginFor<T>, vc_hex: VCHex) -> DispatchResult {
+		///
+		/// `vc_hex` is either an encoded `VC`, or a `VersionedVC` envelope for the newer formats.
+		///
+		/// The weight is charged for the most expensive VC type with the maximum number of issuers
+		/// and signatures, the unused part is refunded.
+		#[pallet::weight(Pallet::<T>::max_store_weight())]
+		pub fn store(origin: OriginFor<T>, vc_hex: VCHex) -> DispatchResultWithPostInfo {
 
 			// Extracting vc from encoded vc byte array
-			let vc: VC<T::Hash> = Self::decode_vc(&vc_hex)?;
-			// Issuer’s Did validity will be checked in the set_approved_issuers() 
-			// Check if owner’s did is registered or not
-      ensure!(<T as pallet::Config>::DidResolution::did_exists(MultiAddress::Did(vc.owner)), Error::<T>::DidDoesNotExist);
-
-      // Check currency code is valid
-      Self::validate_currency_code(&vc)?;
-
-			match vc.vc_type {
-        VCType::TokenVC => {
-          // Check if the origin of the call is approved orgin or not
-					<T as Config>::ApproveOrigin::ensure_origin(origin)?;
-				}
-
-				VCType::SlashTokens | VCType::MintTokens | VCType::TokenTransferVC | VCType::PrivateDidVC | VCType::PublicDidVC => {
-          let sender = ensure_signed(origin)?;
-    
-          // Check If Sender's Did Exists
-          let sender_did = <T as pallet::Config>::DidResolution::get_did(&sender);
-          ensure!(sender_did.is_some(), Error::<T>::DidDoesNotExist);
-          let sender_did = sender_did.unwrap();
-
-					// Validating caller of above VC types
-					Self::validate_vcs(&vc, &sender_did)?;
-				}
-
-				VCType::GenericVC => {
-          let sender = ensure_signed(origin)?;
+			let (vc, extension) = Self::decode_versioned_vc(&vc_hex)?;
+			let weight = Self::store_weight(&vc)?;
+			Self::validate_and_store(origin, vc, extension)?;
+			Ok(Some(weight).into())
+		}
 
-          // Check If Sender's Did Exists
-          let sender_did = <T as pallet::Config>::DidResolution::get_did(&sender);
-          ensure!(sender_did.is_some(), Error::<T>::DidDoesNotExist);
-          let sender_did = sender_did.unwrap();
+		/// Store a new version of a VC, replacing `old_vc_id`
+		///
+		/// The new VC must be of the same type, owned by the current owner of the replaced VC and
+		/// issued by every issuer of the replaced VC, it is validated like any stored VC. Once the
+		/// new VC is active, the replaced VC is deactivated and resolving it returns the new
+		/// version. A pallet depending on the replaced VC, like the token registered from a
+		/// TokenVC, may reject the new version or follow it once it is active.
+		///
+		/// A VC has one new version at a time waiting for signatures. To abandon it, its owner
+		/// deactivates it, and the VC can be superseded again once the abandoned version is
+		/// pruned.
+		#[pallet::weight(Pallet::<T>::max_supersede_weight())]
+		pub fn supersede(origin: OriginFor<T>, old_vc_id: VCid, vc_hex: VCHex) -> DispatchResultWithPostInfo {
+			let (vc, extension) = Self::decode_versioned_vc(&vc_hex)?;
+			let weight = Self::supersede_weight(&vc)?;
+
+			let old_vc = VCs::<T>::get(old_vc_id).ok_or(Error::<T>::VCIdDoesNotExist)?;
+			ensure!(!SupersededBy::<T>::contains_key(old_vc_id), Error::<T>::VCAlreadySuperseded);
+			ensure!(!PendingSupersede::<T>::contains_key(old_vc_id), Error::<T>::SupersedePending);
+			ensure!(vc.vc_type == old_vc.vc_type, Error::<T>::VCTypeMismatch);
+			ensure!(Self::vc_owner(&old_vc_id) == Some(vc.owner), Error::<T>::NotVCOwner);
+			// the new version is only active once all of its issuers have signed it
+			ensure!(
+				old_vc.issuers.iter().all(|issuer| vc.issuers.contains(issuer)),
+				Error::<T>::MissingIssuers
+			);
+
+			// a pallet depending on the replaced VC may restrict its new versions
+			T::VCLinks::can_supersede(&old_vc_id, &old_vc, &vc)?;
+
+			// the chain of versions is bounded, so resolving a VC to its latest version is too
+			let depth = SupersedeDepth::<T>::get(old_vc_id).saturating_add(1);
+			ensure!(depth <= T::MaxSupersedeDepth::get(), Error::<T>::SupersedeChainTooLong);
 
-					// ensure the caller is a council member account
-					ensure!(<T as pallet::Config>::IsCouncilMember::is_collective_member(&sender_did), Error::<T>::NotACouncilMember);
-				}
-      }
-		
-			// Generating vc_id from vc to emit in the event
 			let vc_id: VCid = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+			Supersedes::<T>::insert(vc_id, old_vc_id);
+			SupersedeDepth::<T>::insert(vc_id, depth);
 
-			// storing hash
-			Self::store_vc(vc.owner, vc, vc_id)?;
-			Self::deposit_event(Event::VCValidated{ vcid: vc_id });
-			Ok(())
+			Self::validate_and_store(origin, vc, extension)?;
+			// a new version stored fully signed has already replaced the VC
+			if !SupersededBy::<T>::contains_key(old_vc_id) {
+				PendingSupersede::<T>::insert(old_vc_id, vc_id);
+			}
+			Ok(Some(weight).into())
 		}
-		
+
 		/// Update signature of vc_hash to update status as Active or Inactive
 		///
 		/// This function will set vc status as Active only if all issuers's signatures are verified
-		#[pallet::weight(1)]
-		pub fn add_signature(origin: OriginFor<T>, vc_id: VCid, sign: Signature) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::add_signature(T::MaxIssuers::get()))]
+		pub fn add_signature(origin: OriginFor<T>, vc_id: VCid, sign: MultiSignature) -> DispatchResultWithPostInfo {
 			// Ensure caller is signed account
 			let senders_acccount_id = ensure_signed(origin)?;
-  
-			Self::validate_updater(&senders_acccount_id, &vc_id)?;
-  
-			let mut vc = if let Some(vcs_details)  = VCs::<T>::get(vc_id) {
-				vcs_details
-			} else {
-				fail!(Error::<T>::VCIdDoesNotExist)
-			};
-  
-			Self::validate_sign(&vc, sign.clone(), vc_id)?;
-  
-			vc.signatures.push(sign);
-  
-			Self::update_vc_and_status(vc_id, vc)?;
-			Ok(())
+
+			let issuers = Self::do_add_signature(&senders_acccount_id, vc_id, sign)?;
+			Ok(Some(T::WeightInfo::add_signature(issuers)).into())
+		}
+
+		/// Add signatures to several VCs at once
+		///
+		/// Each signature is validated like in `add_signature`. A signature that fails is skipped
+		/// without affecting the others, the outcome of the batch is reported in
+		/// `SignaturesAdded`.
+		#[pallet::weight(
+			T::WeightInfo::add_signatures(signatures.len() as u32, T::MaxIssuers::get())
+		)]
+		pub fn add_signatures(
+			origin: OriginFor<T>,
+			signatures: BoundedVec<(VCid, MultiSignature), T::MaxSignaturesPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			// Ensure caller is signed account
+			let senders_acccount_id = ensure_signed(origin)?;
+
+			// the batch is charged for the VC with the most issuers
+			let count = signatures.len() as u32;
+			let mut max_issuers = 0;
+			let mut signed = Vec::new();
+			let mut failed = Vec::new();
+			for (vc_id, sign) in signatures.into_iter() {
+				// changes made by a failed signature are reverted
+				let result = frame_support::storage::with_storage_layer(|| {
+					Self::do_add_signature(&senders_acccount_id, vc_id, sign)
+				});
+				match result {
+					Ok(issuers) => {
+						max_issuers = max_issuers.max(issuers);
+						signed.push(vc_id);
+					},
+					Err(e) => {
+						max_issuers = T::MaxIssuers::get();
+						failed.push((vc_id, e));
+					},
+				}
+			}
+
+			Self::deposit_event(Event::SignaturesAdded{ signed, failed });
+			Ok(Some(T::WeightInfo::add_signatures(count, max_issuers)).into())
 		}
 		
 		/// Update status of vc_hash wheather it is active or inactive
-		#[pallet::weight(1)]
+		///
+		/// The owner of the VC can also deactivate it. The storage deposit of the VC stays
+		/// reserved until the VC is pruned, so a reactivated VC is still covered by it.
+		#[pallet::weight(T::WeightInfo::update_status())]
 		pub fn update_status(origin: OriginFor<T>, vc_id: VCid, vc_status: IsVCActive) -> DispatchResult {
 			// Ensure caller is signed account
 			let senders_acccount_id = ensure_signed(origin)?;
-		
-			Self::validate_updater(&senders_acccount_id, &vc_id)?;
+
+			let deactivated_by_owner =
+				!vc_status && Self::ensure_vc_owner(&senders_acccount_id, &vc_id).is_ok();
+			if !deactivated_by_owner {
+				Self::validate_updater(&senders_acccount_id, &vc_id)?;
+			}
 
 			Self::update_vc_status(vc_id, vc_status)?;
 
 			Ok(())
 		}
+
+		/// Request to transfer the VC to another Did
+		///
+		/// The dispatch origin for this call must be `Signed` by the current owner of the VC.
+		/// The transfer is done once all issuers have consented or the transfer origin approves it.
+		/// A pending transfer has to be cancelled before another one is requested.
+		#[pallet::weight(T::WeightInfo::request_transfer())]
+		pub fn request_transfer(origin: OriginFor<T>, vc_id: VCid, new_owner: Did) -> DispatchResult {
+			let senders_acccount_id = ensure_signed(origin)?;
+			let owner = Self::ensure_vc_owner(&senders_acccount_id, &vc_id)?;
+
+			ensure!(!PendingTransfers::<T>::contains_key(vc_id), Error::<T>::TransferPending);
+			ensure!(owner != new_owner, Error::<T>::AlreadyVCOwner);
+			ensure!(<T as pallet::Config>::DidResolution::did_exists(MultiAddress::Did(new_owner)), Error::<T>::DidDoesNotExist);
+
+			PendingTransfers::<T>::insert(vc_id, VCTransfer { new_owner, approvals: vec![] });
+
+			Self::deposit_event(Event::VCTransferRequested{ vcid: vc_id, from: owner, to: new_owner });
+			Ok(())
+		}
+
+		/// Consent to the pending transfer of the VC
+		///
+		/// The dispatch origin for this call must be `Signed` by one of the issuers of the VC.
+		/// The VC is transferred with the consent of the last issuer.
+		#[pallet::weight(T::WeightInfo::approve_transfer(T::MaxIssuers::get()))]
+		pub fn approve_transfer(origin: OriginFor<T>, vc_id: VCid) -> DispatchResultWithPostInfo {
+			let senders_acccount_id = ensure_signed(origin)?;
+			let senders_did = <T as pallet::Config>::DidResolution::get_did(&senders_acccount_id);
+			let senders_did = senders_did.ok_or(Error::<T>::DidDoesNotExist)?;
+
+			let vc = VCs::<T>::get(vc_id).ok_or(Error::<T>::VCIdDoesNotExist)?;
+			ensure!(vc.issuers.contains(&senders_did), Error::<T>::NotAnIssuer);
+
+			let mut transfer = PendingTransfers::<T>::get(vc_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			ensure!(!transfer.approvals.contains(&senders_did), Error::<T>::DuplicateSignature);
+			transfer.approvals.push(senders_did);
+
+			Self::deposit_event(Event::VCTransferApproved{ vcid: vc_id, issuer: senders_did });
+
+			if vc.issuers.iter().all(|issuer| transfer.approvals.contains(issuer)) {
+				Self::transfer_vc(vc_id, transfer.new_owner);
+			} else {
+				PendingTransfers::<T>::insert(vc_id, transfer);
+			}
+			Ok(Some(T::WeightInfo::approve_transfer(vc.issuers.len() as u32)).into())
+		}
+
+		/// Approve the pending transfer of the VC without the issuers' consent
+		///
+		/// The dispatch origin for this call must be the transfer origin.
+		#[pallet::weight(T::WeightInfo::force_approve_transfer())]
+		pub fn force_approve_transfer(origin: OriginFor<T>, vc_id: VCid) -> DispatchResult {
+			<T as Config>::TransferOrigin::ensure_origin(origin)?;
+
+			let transfer = PendingTransfers::<T>::get(vc_id).ok_or(Error::<T>::NoPendingTransfer)?;
+			Self::transfer_vc(vc_id, transfer.new_owner);
+			Ok(())
+		}
+
+		/// Cancel the pending transfer of the VC
+		///
+		/// The dispatch origin for this call must be `Signed` by the current owner of the VC.
+		#[pallet::weight(T::WeightInfo::cancel_transfer())]
+		pub fn cancel_transfer(origin: OriginFor<T>, vc_id: VCid) -> DispatchResult {
+			let senders_acccount_id = ensure_signed(origin)?;
+			Self::ensure_vc_owner(&senders_acccount_id, &vc_id)?;
+
+			ensure!(PendingTransfers::<T>::contains_key(vc_id), Error::<T>::NoPendingTransfer);
+			PendingTransfers::<T>::remove(vc_id);
+
+			Self::deposit_event(Event::VCTransferCancelled{ vcid: vc_id });
+			Ok(())
+		}
 	}
 }
 
@@ -255,6 +586,8 @@ impl<T: Config> Pallet<T> {
 
       Lookup::<T>::insert(vc.owner, vcids);
       RLookup::<T>::insert(vc_id, vc.owner);
+      Self::record_owner_key(vc_id, &vc.owner);
+      Self::index_issuers(vc_id, &vc.issuers);
 
       VCs::<T>::insert(vc_id, vc);
     }
@@ -269,6 +602,31 @@ impl<T: Config> Pallet<T> {
     Ok(vc)
   }
 
+  /// Decode a VC submitted in any of the supported formats, along with its extension
+  pub fn decode_versioned_vc(vc_bytes: &[u8]) -> Result<(VC<T::Hash>, Option<VCExtension>), DispatchError> {
+    let vc = VersionedVC::<T::Hash>::decode_envelope(vc_bytes).map_err(|_| Error::<T>::InvalidVC)?;
+    Ok(vc.into_parts())
+  }
+
+  /// VC as seen by the other pallets, whatever format it was stored in
+  ///
+  /// An expired VC is shown as inactive.
+  pub fn normalized_vc(vc_id: &VCid) -> Option<VC<T::Hash>> {
+    let mut vc = VCs::<T>::get(vc_id)?;
+    if Self::is_expired(vc_id) {
+      vc.is_vc_active = false;
+    }
+    Some(vc)
+  }
+
+  /// If the VC has an expiry and it has passed
+  pub fn is_expired(vc_id: &VCid) -> bool {
+    let now = <frame_system::Pallet<T>>::block_number();
+    VCExtensions::<T>::get(vc_id)
+      .and_then(|extension| extension.expires_at)
+      .map_or(false, |expires_at| now > T::BlockNumber::from(expires_at))
+  }
+
   /// Validate updater
   fn validate_updater(
     senders_acccount_id: &T::AccountId,
@@ -276,9 +634,8 @@ impl<T: Config> Pallet<T> {
   ) -> Result<(), DispatchError> {
     
     // Check if sender's did exists on chain
-    let senders_did = <T as pallet::Config>::DidResolution::get_did(&senders_acccount_id);
-    ensure!(senders_did.is_some(), Error::<T>::DidDoesNotExist);
-    let senders_did = senders_did.unwrap();
+    let senders_did = <T as pallet::Config>::DidResolution::get_did(&senders_acccount_id)
+      .ok_or(Error::<T>::DidDoesNotExist)?;
 
     // Ensure either sender is one of the issuer or member of validator set
     if let Some(vc) = VCs::<T>::get(vc_id) {
@@ -292,6 +649,52 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
+  /// Current owner of the VC
+  ///
+  /// The `owner` inside the VC is the Did it was issued to, it stays as signed by the issuers.
+  /// After a transfer the VC belongs to the Did in `RLookup`.
+  pub fn vc_owner(vc_id: &VCid) -> Option<Did> {
+    RLookup::<T>::try_get(vc_id).ok()
+  }
+
+  /// Ensure the account is the current owner of the VC, returns the owner's Did
+  fn ensure_vc_owner(senders_acccount_id: &T::AccountId, vc_id: &VCid) -> Result<Did, DispatchError> {
+    let owner = Self::vc_owner(vc_id).ok_or(Error::<T>::VCIdDoesNotExist)?;
+    let senders_did = <T as pallet::Config>::DidResolution::get_did(senders_acccount_id);
+    ensure!(senders_did == Some(owner), Error::<T>::NotVCOwner);
+    Ok(owner)
+  }
+
+  /// Record the current key of the owner, a presentation tells if the key was rotated since
+  fn record_owner_key(vc_id: &VCid, owner: &Did) {
+    match <T as pallet::Config>::DidResolution::get_public_key(owner) {
+      Some(public_key) => OwnerKeys::<T>::insert(vc_id, public_key),
+      None => OwnerKeys::<T>::remove(vc_id),
+    }
+  }
+
+  /// Move the VC to the new owner and drop the pending transfer
+  fn transfer_vc(vc_id: VCid, new_owner: Did) {
+    let old_owner = RLookup::<T>::get(vc_id);
+
+    let mut vc_ids = Lookup::<T>::get(old_owner);
+    vc_ids.retain(|id| *id != vc_id);
+    if vc_ids.is_empty() {
+      Lookup::<T>::remove(old_owner);
+    } else {
+      Lookup::<T>::insert(old_owner, vc_ids);
+    }
+
+    let mut vc_ids = Lookup::<T>::get(new_owner);
+    vc_ids.push(vc_id);
+    Lookup::<T>::insert(new_owner, vc_ids);
+    RLookup::<T>::insert(vc_id, new_owner);
+    Self::record_owner_key(&vc_id, &new_owner);
+    PendingTransfers::<T>::remove(vc_id);
+
+    Self::deposit_event(Event::VCTransferred{ vcid: vc_id, from: old_owner, to: new_owner });
+  }
+
   /// Validate slash/token/did vc
   fn validate_vcs(vc: &VC<T::Hash>, sender_did: &Did) -> Result<(), DispatchError> {
     match vc.vc_type {
@@ -299,14 +702,10 @@ impl<T: Config> Pallet<T> {
       VCType::SlashTokens | VCType::MintTokens => {
         let slash_or_mint: SlashMintTokens =
           Self::decode_vc::<SlashMintTokens>(&vc.vc_property)?;
-        let token_vc_struct =
-          if let Some(vc_struct) = VCs::<T>::get(&slash_or_mint.vc_id) {
-            vc_struct
-          } else {
-            fail!(Error::<T>::LinkedVCNotFound);
-          };
+        let token_vc_owner = Self::vc_owner(&Self::latest_vc_id(&slash_or_mint.vc_id))
+          .ok_or(Error::<T>::LinkedVCNotFound)?;
         ensure!(
-          vc.issuers.contains(&token_vc_struct.owner),
+          vc.issuers.contains(&token_vc_owner),
           Error::<T>::VCNotSignedByTokenVCOwner
         );
       },
@@ -315,14 +714,10 @@ impl<T: Config> Pallet<T> {
         // derive Transfer Tokens
         let transfer_tokens: TokenTransferVC = 
 					Self::decode_vc::<TokenTransferVC>(&vc.vc_property)?;
-        let token_vc_struct =
-          if let Some(vc_struct) = VCs::<T>::get(&transfer_tokens.vc_id) {
-            vc_struct
-          } else {
-            fail!(Error::<T>::LinkedVCNotFound);
-          };
+        let token_vc_owner = Self::vc_owner(&Self::latest_vc_id(&transfer_tokens.vc_id))
+          .ok_or(Error::<T>::LinkedVCNotFound)?;
         ensure!(
-          vc.issuers.contains(&token_vc_struct.owner),
+          vc.issuers.contains(&token_vc_owner),
           Error::<T>::VCNotSignedByTokenVCOwner
         );
       },
@@ -330,7 +725,8 @@ impl<T: Config> Pallet<T> {
       VCType::PrivateDidVC => {
         let vc_property = Self::decode_vc::<PrivateDidVC>(&vc.vc_property)?;
         let public_key = vc_property.public_key;
-        let account_id = T::AccountId::decode(&mut &public_key[..]).unwrap();
+        let account_id = public_key.to_account_id::<T::AccountId>()
+          .ok_or(Error::<T>::InvalidPublicKey)?;
 
         // ensure the caller is a council member account
         let did_region = <T as pallet::Config>::IsValidator::get_region(vc_property.did);
@@ -341,7 +737,8 @@ impl<T: Config> Pallet<T> {
       VCType::PublicDidVC => {
         let vc_property = Self::decode_vc::<PublicDidVC>(&vc.vc_property)?;
         let public_key = vc_property.public_key;
-        let account_id = T::AccountId::decode(&mut &public_key[..]).unwrap();
+        let account_id = public_key.to_account_id::<T::AccountId>()
+          .ok_or(Error::<T>::InvalidPublicKey)?;
 
         // ensure the caller is a council member account
         let did_region = <T as pallet::Config>::IsValidator::get_region(vc_property.did);
@@ -385,71 +782,234 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
-  /// Validating VC
-  pub fn is_vc_active(vc: &VC<T::Hash>) -> Result<IsVCActive, DispatchError> {
-    if vc.vc_type != VCType::GenericVC {
-      let hash = T::Hashing::hash_of(&(&vc.vc_type, &vc.vc_property, &vc.owner, &vc.issuers));
-      // ensure the valid hash
-      ensure!(vc.hash.eq(&hash), Error::<T>::VCPropertiesNotVerified);
+  /// The property of a GenericVC must hold a valid CID of the document
+  fn validate_cid(vc: &VC<T::Hash>) -> Result<(), DispatchError> {
+    if vc.vc_type == VCType::GenericVC {
+      let generic_vc = Self::decode_vc::<GenericVC>(&vc.vc_property)?;
+      metamui_vc_verifier::cid::parse_cid(&generic_vc.cid).map_err(|_| Error::<T>::InvalidCid)?;
     }
+    Ok(())
+  }
 
-    // checking for duplicate issuers
-    let mut issuers = vc.issuers.clone();
-    let org_issuer_count = issuers.len();
-    issuers.sort();
-    issuers.dedup();
-    if org_issuer_count != issuers.len() {
-      fail!(Error::<T>::DuplicateSignature);
+  /// CID of the document referenced by a GenericVC, to fetch the document and check it against
+  /// the digest
+  pub fn document_cid(vc_id: VCid) -> Option<Cid> {
+    let vc = VCs::<T>::get(vc_id)?;
+    if vc.vc_type != VCType::GenericVC {
+      return None;
     }
+    let generic_vc = Self::decode_vc::<GenericVC>(&vc.vc_property).ok()?;
+    metamui_vc_verifier::cid::parse_cid(&generic_vc.cid).ok()
+  }
 
-    // checking for duplicate signatures
-    let signatures = vc.signatures.clone();
-    for i in 0..(signatures.len() - 1) {
-      for j in (i + 1)..signatures.len() {
-        if signatures[i] == signatures[j] {
-          fail!(Error::<T>::DuplicateSignature);
-        }
-      }
+  /// Decode the property of a DID VC in the layout it was stored with
+  pub fn decode_did_vc<E: DidVCProperty>(vc_id: &VCid) -> Result<E, DispatchError> {
+    let vc = VCs::<T>::get(vc_id).ok_or(Error::<T>::VCIdDoesNotExist)?;
+    if LegacyDidVCs::<T>::contains_key(vc_id) {
+      Self::decode_vc::<E::Legacy>(&vc.vc_property).map(Into::into)
+    } else {
+      Self::decode_vc::<E>(&vc.vc_property)
     }
+  }
+
+  /// Hash of the VC fields signed by the issuers
+  pub fn vc_hash(vc: &VC<T::Hash>) -> T::Hash {
+    metamui_vc_verifier::hash_of_vc::<T::Hashing>(vc)
+  }
+
+  /// Validating VC, along with the extension of the newer VC formats
+  ///
+  /// The hash of a GenericVC stored before the hash covered its CID is not checked.
+  pub fn is_vc_active(
+    vc_id: &VCid,
+    vc: &VC<T::Hash>,
+    extension: Option<&VCExtension>,
+  ) -> Result<IsVCActive, DispatchError> {
+    let checked = if LegacyGenericVCs::<T>::contains_key(vc_id) {
+      metamui_vc_verifier::check_duplicates(vc)
+    } else {
+      metamui_vc_verifier::check_extended_vc::<T::Hashing>(vc, extension)
+    };
+    checked.map_err(|e| match e {
+      VerifyError::HashMismatch => Error::<T>::VCPropertiesNotVerified,
+      VerifyError::DuplicateIssuer | VerifyError::DuplicateSignature => Error::<T>::DuplicateSignature,
+    })?;
 
     // ensure the caller has all issuers' signature
     if vc.issuers.len() != vc.signatures.len() {
       return Ok(false);
+    }
+    let issuer_keys = vc.issuers.iter()
+      .map(Self::issuer_public_key)
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(metamui_vc_verifier::is_fully_signed(vc, &issuer_keys))
+  }
+
+  /// Public key of an issuer, the issuer's DID has to exist
+  fn issuer_public_key(issuer: &Did) -> Result<PublicKey, DispatchError> {
+    <T as pallet::Config>::DidResolution::get_public_key(issuer)
+      .ok_or_else(|| Error::<T>::DidDoesNotExist.into())
+  }
+
+  /// Add the signature of an issuer to the VC and return the number of issuers of the VC
+  fn do_add_signature(
+    senders_acccount_id: &T::AccountId,
+    vc_id: VCid,
+    sign: MultiSignature,
+  ) -> Result<u32, DispatchError> {
+    Self::validate_updater(senders_acccount_id, &vc_id)?;
+
+    let mut vc = if let Some(vcs_details)  = VCs::<T>::get(vc_id) {
+      vcs_details
     } else {
-      let mut verified_count: usize = 0;
-      for issuer in vc.issuers.iter() {
-        ensure!(<T as pallet::Config>::DidResolution::did_exists(MultiAddress::Did(*issuer)), Error::<T>::DidDoesNotExist);
-        let public_key = <T as pallet::Config>::DidResolution::get_public_key(issuer).unwrap();
-        
-        for signature in vc.signatures.iter() {
-          if signature.verify(vc.hash.as_ref(), &public_key) {
-            verified_count += 1;
-          }
-        }
+      fail!(Error::<T>::VCIdDoesNotExist)
+    };
+
+    Self::validate_sign(&vc, sign.clone(), vc_id)?;
+
+    vc.signatures.push(sign);
+    let issuers = vc.issuers.len() as u32;
+
+    Self::update_vc_and_status(vc_id, vc)?;
+    Ok(issuers)
+  }
+
+  /// Weight of storing the VC, the number of issuers and signatures is bounded
+  fn store_weight(vc: &VC<T::Hash>) -> Result<Weight, DispatchError> {
+    ensure!(vc.issuers.len() <= T::MaxIssuers::get() as usize, Error::<T>::TooManyIssuers);
+    // with more signatures than issuers, one of the issuers has signed twice
+    ensure!(vc.signatures.len() <= vc.issuers.len(), Error::<T>::DuplicateSignature);
+    Ok(Self::store_weight_of(&vc.vc_type, vc.issuers.len() as u32, vc.signatures.len() as u32))
+  }
+
+  /// Weight of storing a VC of the type with `i` issuers and `s` signatures
+  ///
+  /// A TokenVC pays no deposit. A GenericVC is checked against the council, the other types
+  /// against the VCs or Dids they refer to.
+  fn store_weight_of(vc_type: &VCType, i: u32, s: u32) -> Weight {
+    match vc_type {
+      VCType::TokenVC => T::WeightInfo::store(i, s),
+      VCType::GenericVC => T::WeightInfo::store_generic_vc(i, s),
+      _ => T::WeightInfo::store_linked_vc(i, s),
+    }
+  }
+
+  /// Weight of storing a VC of the most expensive type with the most issuers and signatures
+  fn max_store_weight() -> Weight {
+    let max = T::MaxIssuers::get();
+    T::WeightInfo::store(max, max)
+      .max(T::WeightInfo::store_generic_vc(max, max))
+      .max(T::WeightInfo::store_linked_vc(max, max))
+  }
+
+  /// Weight of storing the VC as the new version of another VC
+  ///
+  /// The benchmark supersedes a TokenVC, the other types add their deposit and lookups on top.
+  fn supersede_weight(vc: &VC<T::Hash>) -> Result<Weight, DispatchError> {
+    let (i, s) = (vc.issuers.len() as u32, vc.signatures.len() as u32);
+    let extra = Self::store_weight(vc)?.saturating_sub(T::WeightInfo::store(i, s));
+    Ok(T::WeightInfo::supersede(i, s).saturating_add(extra))
+  }
+
+  /// Weight of superseding a VC with a new version of the most expensive type
+  fn max_supersede_weight() -> Weight {
+    let max = T::MaxIssuers::get();
+    let extra = Self::max_store_weight().saturating_sub(T::WeightInfo::store(max, max));
+    T::WeightInfo::supersede(max, max).saturating_add(extra)
+  }
+
+  /// Validate the caller and the VC, then store it
+  fn validate_and_store(
+    origin: T::Origin,
+    vc: VC<T::Hash>,
+    extension: Option<VCExtension>,
+  ) -> Result<VCid, DispatchError> {
+    // Issuer’s Did validity will be checked in the set_approved_issuers()
+    // Check if owner’s did is registered or not
+    ensure!(<T as pallet::Config>::DidResolution::did_exists(MultiAddress::Did(vc.owner)), Error::<T>::DidDoesNotExist);
+
+    // Check currency code is valid
+    Self::validate_currency_code(&vc)?;
+
+    // Check the document reference is valid
+    Self::validate_cid(&vc)?;
+
+    // TokenVCs come from the approve origin and pay no deposit
+    let depositor = match vc.vc_type {
+      VCType::TokenVC => {
+        // Check if the origin of the call is approved orgin or not
+        <T as Config>::ApproveOrigin::ensure_origin(origin)?;
+        None
       }
-      if verified_count != vc.signatures.len() {
-        return Ok(false);
+
+      VCType::SlashTokens | VCType::MintTokens | VCType::TokenTransferVC | VCType::PrivateDidVC | VCType::PublicDidVC => {
+        let sender = ensure_signed(origin)?;
+
+        // Check If Sender's Did Exists
+        let sender_did = <T as pallet::Config>::DidResolution::get_did(&sender)
+          .ok_or(Error::<T>::DidDoesNotExist)?;
+
+        // Validating caller of above VC types
+        Self::validate_vcs(&vc, &sender_did)?;
+        Some(sender)
       }
-    }
 
-    Ok(true)
+      VCType::GenericVC => {
+        let sender = ensure_signed(origin)?;
+
+        // Check If Sender's Did Exists
+        let sender_did = <T as pallet::Config>::DidResolution::get_did(&sender)
+          .ok_or(Error::<T>::DidDoesNotExist)?;
+
+        // ensure the caller is a council member account
+        ensure!(<T as pallet::Config>::IsCouncilMember::is_collective_member(&sender_did), Error::<T>::NotACouncilMember);
+        Some(sender)
+      }
+    };
+
+    // Generating vc_id from vc to emit in the event
+    let vc_id: VCid = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+    let stored_len = vc.encoded_size().saturating_add(extension.encoded_size());
+
+    // storing hash
+    Self::store_vc(vc.owner, vc, vc_id, extension)?;
+    if let Some(depositor) = depositor {
+      Self::reserve_deposit(vc_id, depositor, stored_len)?;
+    }
+    Self::deposit_event(Event::VCValidated{ vcid: vc_id });
+    Ok(vc_id)
   }
 
   /// Store VC
-  fn store_vc(identifier: Did, mut vc: VC<T::Hash>, vc_id: VCid) -> Result<(), DispatchError> {
+  fn store_vc(
+    identifier: Did,
+    mut vc: VC<T::Hash>,
+    vc_id: VCid,
+    extension: Option<VCExtension>,
+  ) -> Result<(), DispatchError> {
     let current_block_no = <frame_system::Pallet<T>>::block_number();
-    let vc_status = Self::is_vc_active(&vc)?;
+    let vc_status = Self::is_vc_active(&vc_id, &vc, extension.as_ref())?;
 
-    // Check if vc already exists
-    ensure!(!RLookup::<T>::contains_key(&vc_id), Error::<T>::VCAlreadyExists);
+    // Check if vc already exists, or existed and was pruned
+    ensure!(
+      !RLookup::<T>::contains_key(&vc_id) && !PrunedVCs::<T>::contains_key(&vc_id),
+      Error::<T>::VCAlreadyExists
+    );
         
     Self::set_approved_issuers(vc_id, &vc)?;
 
     // Setting is_vc_active
     vc.is_vc_active = vc_status;
+    // A new VC has not been used yet
+    vc.used_count = 0;
 
     VCs::<T>::insert(vc_id, vc.clone());
     RLookup::<T>::insert(vc_id, identifier);
+    Self::record_owner_key(&vc_id, &identifier);
+    if let Some(extension) = extension {
+      VCExtensions::<T>::insert(vc_id, extension);
+    }
 
     if Lookup::<T>::contains_key(&identifier) {
       let mut vc_ids = Lookup::<T>::get(identifier);
@@ -459,15 +1019,185 @@ impl<T: Config> Pallet<T> {
       Lookup::<T>::insert(identifier, vec![vc_id]);
     }
 
+    Self::index_issuers(vc_id, &vc.issuers);
+
     VCHistory::<T>::insert(vc_id, (vc_status, current_block_no));
 
+    if vc_status {
+      Self::replace_superseded(vc_id)?;
+    }
+
+    Ok(())
+  }
+
+  /// Storage deposit of a VC of the given encoded length
+  pub fn vc_deposit(len: usize) -> BalanceOf<T> {
+    let len: BalanceOf<T> = (len as u32).into();
+    T::VCDepositBase::get().saturating_add(T::VCDepositPerByte::get().saturating_mul(len))
+  }
+
+  /// Reserve the storage deposit of the VC from the submitter
+  fn reserve_deposit(vc_id: VCid, who: T::AccountId, len: usize) -> Result<(), DispatchError> {
+    let amount = Self::vc_deposit(len);
+    if amount.is_zero() {
+      return Ok(());
+    }
+    T::Currency::reserve(&who, amount)?;
+    VCDeposits::<T>::insert(vc_id, (who.clone(), amount));
+    Self::deposit_event(Event::VCDepositReserved{ vcid: vc_id, who, amount });
+    Ok(())
+  }
+
+  /// Return the storage deposit of the VC to the submitter, if any is held
+  fn release_deposit(vc_id: VCid) {
+    if let Some((who, amount)) = VCDeposits::<T>::take(vc_id) {
+      T::Currency::unreserve(&who, amount);
+      Self::deposit_event(Event::VCDepositReleased{ vcid: vc_id, who, amount });
+    }
+  }
+
+  /// Deactivate the VC replaced by the given VC once the new version is active
+  fn replace_superseded(vc_id: VCid) -> Result<(), DispatchError> {
+    if let Some(old_vc_id) = Supersedes::<T>::get(vc_id) {
+      if !SupersededBy::<T>::contains_key(old_vc_id) {
+        // the replaced VC may already be pruned
+        if let Some(old_vc) = VCs::<T>::get(old_vc_id) {
+          let vc = VCs::<T>::get(vc_id).ok_or(Error::<T>::VCIdDoesNotExist)?;
+          T::VCLinks::on_superseded(&old_vc_id, &old_vc, &vc_id, &vc)?;
+          Self::update_vc_status(old_vc_id, false)?;
+        }
+        SupersededBy::<T>::insert(old_vc_id, vc_id);
+        PendingSupersede::<T>::remove(old_vc_id);
+        Self::deposit_event(Event::VCSuperseded{ vcid: old_vc_id, by: vc_id });
+      }
+    }
     Ok(())
   }
 
+  /// Id of the latest version of the VC, following the chain of replacements
+  ///
+  /// A chain has at most `MaxSupersedeDepth` replacements.
+  pub fn latest_vc_id(vc_id: &VCid) -> VCid {
+    let mut latest = *vc_id;
+    for _ in 0..T::MaxSupersedeDepth::get() {
+      match SupersededBy::<T>::get(latest) {
+        Some(next) => latest = next,
+        None => break,
+      }
+    }
+    latest
+  }
+
+  /// Add the VC to the issuer index of each of its issuers
+  fn index_issuers(vc_id: VCid, issuers: &[Did]) {
+    for issuer in issuers.iter() {
+      let mut vc_ids = IssuerLookup::<T>::get(issuer);
+      vc_ids.push(vc_id);
+      IssuerLookup::<T>::insert(issuer, vc_ids);
+    }
+  }
+
+  /// VCs currently owned by the Did, at most `limit` of them starting at `offset`
+  pub fn vcs_of_owner(did: Did, offset: u32, limit: u32) -> Vec<(VCid, VC<T::Hash>)> {
+    Self::page_of_vcs(Lookup::<T>::get(did), offset, limit)
+  }
+
+  /// VCs issued by the Did, at most `limit` of them starting at `offset`
+  pub fn vcs_issued_by(did: Did, offset: u32, limit: u32) -> Vec<(VCid, VC<T::Hash>)> {
+    Self::page_of_vcs(IssuerLookup::<T>::get(did), offset, limit)
+  }
+
+  fn page_of_vcs(vc_ids: Vec<VCid>, offset: u32, limit: u32) -> Vec<(VCid, VC<T::Hash>)> {
+    vc_ids.into_iter()
+      .skip(offset as usize)
+      .take(limit as usize)
+      .filter_map(|vc_id| VCs::<T>::get(vc_id).map(|vc| (vc_id, vc)))
+      .collect()
+  }
+
+  /// Current status of the VC
+  pub fn vc_status(vc_id: VCid) -> Option<VCStatus> {
+    let vc = Self::normalized_vc(&vc_id)?;
+    Some(VCStatus {
+      owner: Self::vc_owner(&vc_id)?,
+      is_vc_active: vc.is_vc_active,
+      is_vc_used: vc.is_vc_used(),
+      used_count: vc.used_count,
+      max_uses: vc.max_uses,
+    })
+  }
+
+  /// Payload signed by the owner to present the VC, the SCALE encoding of
+  /// `(PRESENTATION_CONTEXT, vc_id, challenge)`
+  ///
+  /// That is the 23 bytes of `b"metamui/vc-presentation"`, the 32 bytes of the VC id, then the
+  /// compact encoded length of the challenge followed by its bytes. The context keeps a
+  /// presentation signature from being replayed as a signature over anything else.
+  pub fn presentation_payload(vc_id: &VCid, challenge: &[u8]) -> Vec<u8> {
+    (PRESENTATION_CONTEXT, vc_id, challenge).encode()
+  }
+
+  /// Verify that the presentation payload of the challenge is signed by the current owner of
+  /// the VC, along with the status of the VC
+  pub fn verify_presentation(
+    vc_id: VCid,
+    challenge: &[u8],
+    owner_signature: &MultiSignature,
+  ) -> Option<PresentationVerdict> {
+    let vc = VCs::<T>::get(vc_id)?;
+    let owner = Self::vc_owner(&vc_id)?;
+
+    // the issuers may have rotated their keys since the VC was signed
+    let extension = VCExtensions::<T>::get(vc_id);
+    let is_vc_active = vc.is_vc_active
+      && !Self::is_expired(&vc_id)
+      && Self::is_vc_active(&vc_id, &vc, extension.as_ref()).unwrap_or(false);
+    let is_revoked = !vc.is_vc_active && Self::pending_signatures(vc_id).is_empty();
+
+    let owner_key = <T as pallet::Config>::DidResolution::get_public_key(&owner);
+    let is_owner_key_match = owner_key.is_some() && owner_key == OwnerKeys::<T>::get(vc_id);
+    let is_signature_valid = owner_key
+      .map(|public_key| {
+        public_key.verify_signature(owner_signature, &Self::presentation_payload(&vc_id, challenge))
+      })
+      .unwrap_or(false);
+
+    Some(PresentationVerdict {
+      owner,
+      is_vc_active,
+      is_vc_used: vc.is_vc_used(),
+      is_revoked,
+      is_owner_key_match,
+      is_signature_valid,
+    })
+  }
+
+  /// Issuers of the VC who have not signed it yet
+  pub fn pending_signatures(vc_id: VCid) -> Vec<Did> {
+    let vc = match VCs::<T>::get(vc_id) {
+      Some(vc) => vc,
+      None => return Vec::new(),
+    };
+    let approvers = VCApproverList::<T>::get(vc_id);
+    vc.issuers.into_iter().filter(|issuer| !approvers.contains(issuer)).collect()
+  }
+
   /// Update VC from storage
   fn update_vc_status(vc_id: VCid, status: IsVCActive) -> Result<(), DispatchError> {
+    // a replaced VC can not be activated again
+    ensure!(!status || !SupersededBy::<T>::contains_key(vc_id), Error::<T>::VCAlreadySuperseded);
+
     if let Some(mut vc) = VCs::<T>::get(&vc_id) {
 
+      // A revoked VC is pruned after the retention period, unless it is activated again
+      if !status {
+        if !RetiredAt::<T>::contains_key(vc_id) {
+          Self::retire(vc_id)?;
+        }
+      } else if !vc.is_vc_used() {
+        Self::unretire(vc_id);
+      }
+
       // Setting is_vc_active
       vc.is_vc_active = status;
 
@@ -481,14 +1211,19 @@ impl<T: Config> Pallet<T> {
     }
     Self::deposit_event(Event::VCStatusUpdated{ vcid: vc_id, vcstatus: status });
 
+    if status {
+      Self::replace_superseded(vc_id)?;
+    }
+
     Ok(())
   }
 
   // Update VC and vc_status from storage
   fn update_vc_and_status(vc_id: VCid, mut updated_vc: VC<T::Hash>) -> Result<(), DispatchError> {
 
-    // Setting is_vc_active
-    let status = Self::is_vc_active(&updated_vc)?;
+    // Setting is_vc_active, a replaced VC stays inactive
+    let extension = VCExtensions::<T>::get(vc_id);
+    let status = Self::is_vc_active(&vc_id, &updated_vc, extension.as_ref())? && !SupersededBy::<T>::contains_key(vc_id);
     updated_vc.is_vc_active = status;
     VCs::<T>::insert(vc_id, updated_vc);
 
@@ -498,26 +1233,257 @@ impl<T: Config> Pallet<T> {
 
     Self::deposit_event(Event::VCStatusUpdated{ vcid: vc_id, vcstatus: status });
 
+    if status {
+      Self::replace_superseded(vc_id)?;
+    }
+
     Ok(())
   }
 
-  /// Update vc's is_used flag to true
-  pub fn set_is_used_flag(vc_id: VCid, is_vc_used: Option<bool>) {
-    if let Some(mut vc) = VCs::<T>::get(&vc_id) {
-      vc.is_vc_used = is_vc_used.unwrap_or(true);
-      VCs::<T>::insert(vc_id, vc);
+  /// Consume one use of the vc and return the remaining uses
+  pub fn consume(vc_id: VCid) -> Result<Option<u32>, DispatchError> {
+    let mut vc = if let Some(vc) = VCs::<T>::get(&vc_id) {
+      vc
+    } else {
+      fail!(Error::<T>::VCIdDoesNotExist)
+    };
+
+    ensure!(!vc.is_vc_used(), Error::<T>::VCAlreadyUsed);
+    ensure!(!Self::is_expired(&vc_id), Error::<T>::VCExpired);
+
+    vc.used_count = vc.used_count.saturating_add(1);
+    let remaining_uses = vc.remaining_uses();
+    if vc.is_vc_used() {
+      Self::retire(vc_id)?;
+    }
+    VCs::<T>::insert(vc_id, vc);
+
+    Self::deposit_event(Event::VCConsumed{ vcid: vc_id, remaining_uses });
+
+    Ok(remaining_uses)
+  }
+
+  /// Schedule the VC to be pruned once the retention period has passed
+  ///
+  /// When the prune queue of the current block is full, the VC is queued in the next block with
+  /// room left, at most `MAX_RETIRE_SCAN` blocks ahead.
+  fn retire(vc_id: VCid) -> Result<(), DispatchError> {
+    Self::queue_retired(vc_id, MAX_RETIRE_SCAN)
+  }
+
+  /// Take the VC out of the prune queue it was retired in, if any
+  fn unretire(vc_id: VCid) {
+    if let Some(block) = RetiredAt::<T>::take(vc_id) {
+      PruneQueue::<T>::mutate_exists(block, |queue| {
+        if let Some(vc_ids) = queue {
+          vc_ids.retain(|id| *id != vc_id);
+          if vc_ids.is_empty() {
+            *queue = None;
+          }
+        }
+      });
+    }
+  }
+
+  /// Queue the retired VC in the first of the next `max_blocks` blocks with room left
+  pub(crate) fn queue_retired(vc_id: VCid, max_blocks: u32) -> Result<(), DispatchError> {
+    let mut block = <frame_system::Pallet<T>>::block_number();
+    for _ in 0..max_blocks {
+      if PruneQueue::<T>::try_append(block, vc_id).is_ok() {
+        RetiredAt::<T>::insert(vc_id, block);
+        return Ok(());
+      }
+      block += One::one();
+    }
+    fail!(Error::<T>::PruneQueueFull)
+  }
+
+  /// Prune the VCs whose retention period has passed, within the given weight
+  ///
+  /// The prune queue is processed block by block from the cursor. At most `MaxPrunedPerBlock`
+  /// VCs are pruned in a call, each empty block of the queue visited counts as a pruned VC.
+  pub fn prune_vcs(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+    let db_weight = T::DbWeight::get();
+    // reading and writing the queue of a block
+    let queue_weight = db_weight.reads_writes(1, 1);
+    // pruning a VC with the most issuers
+    let prune_weight = T::WeightInfo::prune_vc(T::MaxIssuers::get());
+
+    let mut weight = db_weight.reads_writes(1, 1);
+    if weight.saturating_add(queue_weight) > remaining_weight {
+      return 0;
+    }
+    let due = match now.checked_sub(&T::RetentionPeriod::get()) {
+      Some(due) => due,
+      None => return 0,
+    };
+
+    let max_pruned = T::MaxPrunedPerBlock::get();
+    let mut pruned: u32 = 0;
+    let mut cursor = PruneCursor::<T>::get();
+    while cursor <= due && pruned < max_pruned {
+      if weight.saturating_add(queue_weight) > remaining_weight {
+        break;
+      }
+      weight = weight.saturating_add(queue_weight);
+
+      let mut vc_ids = PruneQueue::<T>::get(cursor);
+      if vc_ids.is_empty() {
+        pruned += 1;
+      }
+      while pruned < max_pruned && weight.saturating_add(prune_weight) <= remaining_weight {
+        match vc_ids.pop() {
+          Some(vc_id) => {
+            weight = weight.saturating_add(Self::prune_vc(vc_id, cursor));
+            pruned += 1;
+          },
+          None => break,
+        }
+      }
+
+      if !vc_ids.is_empty() {
+        PruneQueue::<T>::insert(cursor, vc_ids);
+        break;
+      }
+      PruneQueue::<T>::remove(cursor);
+      cursor += One::one();
+    }
+    PruneCursor::<T>::put(cursor);
+
+    weight
+  }
+
+  /// Remove the VC retired in the given block, keeping its id so it can not be stored again
+  ///
+  /// The VC is kept if it was activated or retired again since. Returns the weight used.
+  fn prune_vc(vc_id: VCid, retired_at: T::BlockNumber) -> Weight {
+    let db_weight = T::DbWeight::get();
+    if RetiredAt::<T>::get(vc_id) != Some(retired_at) {
+      return db_weight.reads(1);
+    }
+    RetiredAt::<T>::remove(vc_id);
+    // a VC another pallet depends on stays stored, it is only taken off the queue
+    if T::VCLinks::is_vc_linked(&vc_id) {
+      return db_weight.reads_writes(2, 1);
+    }
+
+    let mut issuers: u32 = 0;
+    if let Some(vc) = VCs::<T>::take(vc_id) {
+      for issuer in vc.issuers.iter() {
+        IssuerLookup::<T>::mutate_exists(issuer, |vc_ids| Self::remove_vc_id(vc_ids, &vc_id));
+        issuers += 1;
+      }
+    }
+    if RLookup::<T>::contains_key(vc_id) {
+      let owner = RLookup::<T>::take(vc_id);
+      Lookup::<T>::mutate_exists(owner, |vc_ids| Self::remove_vc_id(vc_ids, &vc_id));
+    }
+    VCHistory::<T>::remove(vc_id);
+    VCApproverList::<T>::remove(vc_id);
+    RemainingAmount::<T>::remove(vc_id);
+    PendingTransfers::<T>::remove(vc_id);
+    // the link to a newer version is kept, so the pruned VC still resolves to it
+    if let Some(old_vc_id) = Supersedes::<T>::take(vc_id) {
+      // an abandoned new version no longer holds back the VC it was to replace
+      if PendingSupersede::<T>::get(old_vc_id) == Some(vc_id) {
+        PendingSupersede::<T>::remove(old_vc_id);
+      }
+    }
+    PendingSupersede::<T>::remove(vc_id);
+    SupersedeDepth::<T>::remove(vc_id);
+    VCExtensions::<T>::remove(vc_id);
+    LegacyDidVCs::<T>::remove(vc_id);
+    LegacyGenericVCs::<T>::remove(vc_id);
+    OwnerKeys::<T>::remove(vc_id);
+    Self::release_deposit(vc_id);
+    PrunedVCs::<T>::insert(vc_id, ());
+
+    Self::deposit_event(Event::VCPruned{ vcid: vc_id });
+
+    T::WeightInfo::prune_vc(issuers)
+  }
+
+  fn remove_vc_id(vc_ids: &mut Option<Vec<VCid>>, vc_id: &VCid) {
+    if let Some(ids) = vc_ids {
+      ids.retain(|id| id != vc_id);
+      if ids.is_empty() {
+        *vc_ids = None;
+      }
+    }
+  }
+
+  /// Amount authorised by a SlashTokens, MintTokens or TokenTransferVC vc
+  fn vc_amount(vc: &VC<T::Hash>) -> Result<u128, DispatchError> {
+    let amount = match vc.vc_type {
+      VCType::SlashTokens | VCType::MintTokens => {
+        Self::decode_vc::<SlashMintTokens>(&vc.vc_property)?.amount
+      },
+      VCType::TokenTransferVC => {
+        Self::decode_vc::<TokenTransferVC>(&vc.vc_property)?.amount
+      },
+      _ => fail!(Error::<T>::VCHasNoAmount),
+    };
+    Ok(amount)
+  }
+
+  /// Amount left in the current use of the vc
+  pub fn remaining_amount(vc_id: VCid) -> Result<u128, DispatchError> {
+    let vc = if let Some(vc) = VCs::<T>::get(&vc_id) {
+      vc
+    } else {
+      fail!(Error::<T>::VCIdDoesNotExist)
+    };
+
+    match RemainingAmount::<T>::get(&vc_id) {
+      Some(amount) => Ok(amount),
+      None => Self::vc_amount(&vc),
     }
   }
 
+  /// Consume part of the amount authorised by the vc and return what is left
+  ///
+  /// The vc is consumed only once its amount is exhausted
+  pub fn consume_amount(vc_id: VCid, amount: u128) -> Result<u128, DispatchError> {
+    let vc = if let Some(vc) = VCs::<T>::get(&vc_id) {
+      vc
+    } else {
+      fail!(Error::<T>::VCIdDoesNotExist)
+    };
+
+    ensure!(!vc.is_vc_used(), Error::<T>::VCAlreadyUsed);
+    ensure!(!Self::is_expired(&vc_id), Error::<T>::VCExpired);
+    ensure!(amount > 0, Error::<T>::InvalidAmount);
+
+    let full_amount = Self::vc_amount(&vc)?;
+    let remaining_amount = RemainingAmount::<T>::get(&vc_id).unwrap_or(full_amount);
+    ensure!(amount <= remaining_amount, Error::<T>::AmountExceedsRemaining);
+
+    let mut remaining_amount = remaining_amount - amount;
+    if remaining_amount == 0 {
+      // amount exhausted, the next use of the vc starts with the full amount again
+      if Self::consume(vc_id)? == Some(0) {
+        RemainingAmount::<T>::remove(&vc_id);
+      } else {
+        remaining_amount = full_amount;
+        RemainingAmount::<T>::insert(&vc_id, remaining_amount);
+      }
+    } else {
+      RemainingAmount::<T>::insert(&vc_id, remaining_amount);
+    }
+
+    Self::deposit_event(Event::VCAmountConsumed{ vcid: vc_id, amount, remaining_amount });
+
+    Ok(remaining_amount)
+  }
+
   // Validate sign
-  fn validate_sign(vc: &VC<T::Hash>, sign: Signature, vc_id: VCid) -> Result<(), DispatchError> {
+  fn validate_sign(vc: &VC<T::Hash>, sign: MultiSignature, vc_id: VCid) -> Result<(), DispatchError> {
     let mut is_sign_valid = false;
     let mut vc_approver_list = VCApproverList::<T>::get(vc_id);
     for issuer in vc.issuers.iter() {
-      ensure!(<T as pallet::Config>::DidResolution::did_exists(MultiAddress::Did(*issuer)), Error::<T>::DidDoesNotExist);
-      let public_key = <T as pallet::Config>::DidResolution::get_public_key(&issuer).unwrap();
-      
-      if sign.verify(vc.hash.as_ref(), &public_key) {
+      let public_key = Self::issuer_public_key(issuer)?;
+
+      if metamui_vc_verifier::verify_signature(&public_key, &sign, &vc.hash) {
         if vc_approver_list.contains(&issuer) {
           fail!(Error::<T>::DuplicateSignature);
         }
@@ -540,10 +1506,9 @@ impl<T: Config> Pallet<T> {
       let sign = &signatures[i];
       let mut is_sign_valid = false;
       for issuer in vc.issuers.iter() {
-        ensure!(<T as pallet::Config>::DidResolution::did_exists(MultiAddress::Did(*issuer)), Error::<T>::DidDoesNotExist);
-        let public_key = <T as pallet::Config>::DidResolution::get_public_key(issuer).unwrap();
+        let public_key = Self::issuer_public_key(issuer)?;
 
-        if sign.verify(vc.hash.as_ref(), &public_key) {
+        if metamui_vc_verifier::verify_signature(&public_key, sign, &vc.hash) {
           if vc_approver_list.contains(&issuer) {
             fail!(Error::<T>::DuplicateSignature);
           }
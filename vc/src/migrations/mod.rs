@@ -0,0 +1,10 @@
+/// Migration from the single `is_vc_used` flag to usage counters
+pub mod v1;
+/// Migration from sr25519 only signatures to multi-scheme signatures
+pub mod v2;
+/// Index of the VCs by issuer
+pub mod v3;
+/// Pruning of used and revoked VCs
+pub mod v4;
+/// Hash check of the GenericVCs stored before the hash covered their CID
+pub mod v5;
@@ -0,0 +1,50 @@
+use crate::*;
+use frame_support::{
+  traits::{Get, GetStorageVersion},
+  weights::Weight,
+};
+use metamui_primitives::types::VCProperty;
+use sp_core::sr25519;
+
+/// VC layout before usage counters were introduced
+#[derive(Decode)]
+struct OldVC<Hash> {
+  hash: Hash,
+  owner: Did,
+  issuers: Vec<Did>,
+  signatures: Vec<sr25519::Signature>,
+  is_vc_used: bool,
+  is_vc_active: bool,
+  vc_type: VCType,
+  vc_property: VCProperty,
+}
+
+/// Convert every stored VC to a single use VC, keeping its used state
+///
+/// The DID VCs are marked to keep decoding their property with the sr25519 key.
+pub fn migrate<T: Config>() -> Weight {
+  let mut translated: u64 = 0;
+  let mut marked: u64 = 0;
+  VCs::<T>::translate::<OldVC<T::Hash>, _>(|vc_id, old| {
+    translated += 1;
+    if super::v2::mark_legacy_did_vc::<T>(vc_id, &old.vc_type) {
+      marked += 1;
+    }
+    let vc = super::v2::OldVC {
+      hash: old.hash,
+      owner: old.owner,
+      issuers: old.issuers,
+      signatures: old.signatures,
+      max_uses: Some(1),
+      used_count: if old.is_vc_used { 1 } else { 0 },
+      is_vc_active: old.is_vc_active,
+      vc_type: old.vc_type,
+      vc_property: old.vc_property,
+    };
+    Some(vc.into())
+  });
+
+  Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+  T::DbWeight::get().reads_writes(translated + 1, translated + marked + 1)
+}
@@ -0,0 +1,70 @@
+use crate::*;
+use frame_support::{
+  traits::{Get, GetStorageVersion},
+  weights::Weight,
+};
+use metamui_primitives::types::VCProperty;
+use sp_core::sr25519;
+
+/// VC layout before multi-scheme signatures were introduced
+#[derive(Decode)]
+pub(super) struct OldVC<Hash> {
+  pub hash: Hash,
+  pub owner: Did,
+  pub issuers: Vec<Did>,
+  pub signatures: Vec<sr25519::Signature>,
+  pub max_uses: Option<u32>,
+  pub used_count: u32,
+  pub is_vc_active: bool,
+  pub vc_type: VCType,
+  pub vc_property: VCProperty,
+}
+
+impl<Hash> From<OldVC<Hash>> for VC<Hash> {
+  fn from(old: OldVC<Hash>) -> Self {
+    VC {
+      hash: old.hash,
+      owner: old.owner,
+      issuers: old.issuers,
+      signatures: old.signatures.into_iter().map(MultiSignature::Sr25519).collect(),
+      max_uses: old.max_uses,
+      used_count: old.used_count,
+      is_vc_active: old.is_vc_active,
+      vc_type: old.vc_type,
+      vc_property: old.vc_property,
+    }
+  }
+}
+
+/// Mark a DID VC as holding the sr25519 key layout, returns if the VC was marked
+///
+/// The property can not be re-encoded, as the issuers' signatures cover it. The marked VCs are
+/// decoded with the legacy layout instead.
+pub(super) fn mark_legacy_did_vc<T: Config>(vc_id: VCid, vc_type: &VCType) -> bool {
+  match vc_type {
+    VCType::PublicDidVC | VCType::PrivateDidVC => {
+      LegacyDidVCs::<T>::insert(vc_id, ());
+      true
+    },
+    _ => false,
+  }
+}
+
+/// Wrap the sr25519 signatures of every stored VC into multi-scheme signatures
+///
+/// The DID VCs are marked to keep decoding their property with the sr25519 key.
+pub fn migrate<T: Config>() -> Weight {
+  let mut translated: u64 = 0;
+  let mut marked: u64 = 0;
+  VCs::<T>::translate::<OldVC<T::Hash>, _>(|vc_id, old| {
+    translated += 1;
+    if mark_legacy_did_vc::<T>(vc_id, &old.vc_type) {
+      marked += 1;
+    }
+    Some(old.into())
+  });
+
+  Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+  T::DbWeight::get().reads_writes(translated + 1, translated + marked + 1)
+}
@@ -0,0 +1,24 @@
+use crate::*;
+use frame_support::{
+  traits::{Get, GetStorageVersion},
+  weights::Weight,
+};
+
+/// Build the issuer index from the VCs already stored
+pub fn migrate<T: Config>() -> Weight {
+  let mut read: u64 = 0;
+  let mut indexed: u64 = 0;
+  for (vc_id, vc) in VCs::<T>::iter() {
+    read += 1;
+    for issuer in vc.issuers.iter() {
+      let mut vc_ids = IssuerLookup::<T>::get(issuer);
+      vc_ids.push(vc_id);
+      IssuerLookup::<T>::insert(issuer, vc_ids);
+      indexed += 1;
+    }
+  }
+
+  Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+  T::DbWeight::get().reads_writes(read + indexed + 1, indexed + 1)
+}
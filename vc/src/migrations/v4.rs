@@ -0,0 +1,30 @@
+use crate::*;
+use frame_support::{
+  traits::{Get, GetStorageVersion},
+  weights::Weight,
+};
+
+/// Start the prune queue at the current block and schedule the VCs already used or revoked
+///
+/// A VC that is inactive only because signatures are still missing is not retired.
+pub fn migrate<T: Config>() -> Weight {
+  let now = <frame_system::Pallet<T>>::block_number();
+  PruneCursor::<T>::put(now);
+
+  let mut read: u64 = 0;
+  let mut retired: u64 = 0;
+  for (vc_id, vc) in VCs::<T>::iter() {
+    read += 1;
+    let is_revoked = !vc.is_vc_active &&
+      VCApproverList::<T>::get(vc_id).len() == vc.issuers.len();
+    if vc.is_vc_used() || is_revoked {
+      // spread over as many blocks as needed once the queue of a block is full
+      let _ = Pallet::<T>::queue_retired(vc_id, u32::MAX);
+      retired += 1;
+    }
+  }
+
+  Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+  T::DbWeight::get().reads_writes(2 * read + 1, 2 * retired + 2)
+}
@@ -0,0 +1,31 @@
+use crate::*;
+use frame_support::{
+  traits::{Get, GetStorageVersion},
+  weights::Weight,
+};
+
+/// Mark the GenericVCs whose hash does not cover their CID, so they stay valid
+///
+/// The hash of a GenericVC was not checked before, the VCs issued with another hash keep being
+/// accepted while the ones stored from now on must bind their CID.
+pub fn migrate<T: Config>() -> Weight {
+  let mut read: u64 = 0;
+  let mut marked: u64 = 0;
+  for (vc_id, vc) in VCs::<T>::iter() {
+    read += 1;
+    if vc.vc_type != VCType::GenericVC {
+      continue;
+    }
+    read += 1;
+    let extension = VCExtensions::<T>::get(vc_id);
+    let checked = metamui_vc_verifier::check_extended_vc::<T::Hashing>(&vc, extension.as_ref());
+    if checked == Err(VerifyError::HashMismatch) {
+      LegacyGenericVCs::<T>::insert(vc_id, ());
+      marked += 1;
+    }
+  }
+
+  Pallet::<T>::current_storage_version().put::<Pallet<T>>();
+
+  T::DbWeight::get().reads_writes(read + 1, marked + 1)
+}
@@ -1,16 +1,18 @@
 use crate::{self as verified_credentials, Config};
 use frame_support::{
 	ord_parameter_types, parameter_types,
-	traits::{ConstU32, Everything, GenesisBuild},
+	traits::{ConstU32, ConstU64, Everything, GenesisBuild},
 };
 use frame_system::{EnsureSigned, EnsureSignedBy};
-use pallet_did::types::{DIdentity, PrivateDid};
-use sp_core::{sr25519, Pair, H256};
+use pallet_did::types::{DIdentity, PrivateDid, PublicKey};
+use sp_core::{ecdsa, ed25519, sr25519, Pair, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
 };
 use sp_std::convert::TryInto;
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use std::sync::Arc;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 type BlockNumber = u32;
@@ -32,6 +34,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		VC: verified_credentials::{Pallet, Call, Storage, Event<T>},
 		ValidatorSet: pallet_validator_set::{Pallet, Call, Storage, Event<T>, Config<T>},
 		Did: pallet_did::{Pallet, Call, Storage, Config<T>, Event<T>},
@@ -67,7 +70,7 @@ impl frame_system::Config for Test {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -82,6 +85,32 @@ impl Config for Test {
 	type IsCouncilMember = Council;
 	type IsValidator = ValidatorCommittee;
 	type DidResolution = Did;
+	type TransferOrigin = frame_system::EnsureRoot<u64>;
+	type RetentionPeriod = ConstU64<10>;
+	type MaxPrunedPerBlock = ConstU32<2>;
+	type MaxRetiredPerBlock = ConstU32<3>;
+	type MaxSupersedeDepth = ConstU32<2>;
+	type MaxIssuers = ConstU32<10>;
+	type MaxSignaturesPerBatch = ConstU32<4>;
+	type Currency = Balances;
+	type VCDepositBase = ConstU64<10>;
+	type VCDepositPerByte = ConstU64<1>;
+	type VCLinks = ();
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type Balance = u64;
+	type DustRemoval = ();
+	type Event = Event;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = ();
+	type MaxReserves = ConstU32<2>;
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type DidResolution = Did;
+	type ApproveOrigin = frame_system::EnsureRoot<u64>;
 }
 
 ord_parameter_types! {
@@ -147,14 +176,17 @@ impl pallet_validator_collective::Config<ValidatorCollective> for Test {
 
 pub const VALIDATOR_ACCOUNT: u64 = 0;
 pub const VALIDATOR_DID: [u8; 32] = *b"did:ssid:Alice\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
-pub const VALIDATOR_PUBKEY: sr25519::Public = sr25519::Public([0; 32]);
+pub const VALIDATOR_PUBKEY: PublicKey = PublicKey::Sr25519(sr25519::Public([0; 32]));
 pub const NON_VALIDATOR_ACCOUNT: u64 = 2;
 pub const ALICE: metamui_primitives::Did = *b"did:ssid:swn\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 pub const BOB: metamui_primitives::Did = *b"did:ssid:bob\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 pub const DAVE: metamui_primitives::Did = *b"did:ssid:dave\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 pub const EVE: metamui_primitives::Did = *b"did:ssid:eve\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+pub const FERDIE: metamui_primitives::Did = *b"did:ssid:ferdie\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
+pub const CHARLIE: metamui_primitives::Did = *b"did:ssid:charlie\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0";
 pub const DAVE_ACCOUNT_ID: u64 = 13620103657161844528;
 pub const BOB_ACCOUNT_ID: u64 = 7166219960988249998;
+pub const INITIAL_BALANCE: u64 = 100_000;
 pub const BOB_SEED: [u8; 32] = [
 	57, 143, 12, 40, 249, 136, 133, 224, 70, 51, 61, 74, 65, 193, 156, 238, 76, 55, 54, 138, 152,
 	50, 198, 80, 47, 108, 253, 24, 46, 42, 239, 137,
@@ -167,12 +199,26 @@ pub const EVE_SEED: [u8; 32] = [
 	120, 106, 208, 226, 223, 69, 111, 228, 61, 209, 249, 30, 188, 162, 46, 35, 91, 193, 98, 224,
 	187, 141, 83, 198, 51, 232, 200, 91, 42, 246, 139, 122,
 ];
+/// Seed of the ed25519 key held by FERDIE
+pub const FERDIE_SEED: [u8; 32] = [7; 32];
+/// Seed of the ecdsa key held by CHARLIE
+pub const CHARLIE_SEED: [u8; 32] = [9; 32];
 
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut o = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(BOB_ACCOUNT_ID, INITIAL_BALANCE),
+			(DAVE_ACCOUNT_ID, INITIAL_BALANCE),
+			(VALIDATOR_ACCOUNT, INITIAL_BALANCE),
+		],
+	}
+	.assimilate_storage(&mut o)
+	.unwrap();
+
 	pallet_validator_set::GenesisConfig::<Test> {
 		members: frame_support::bounded_vec![BOB, DAVE],
 		phantom: Default::default(),
@@ -184,12 +230,12 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		initial_dids: vec![
 			DIdentity::Private(PrivateDid {
 				identifier: BOB,
-				public_key: sr25519::Pair::from_seed(&BOB_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&BOB_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 			DIdentity::Private(PrivateDid {
 				identifier: DAVE,
-				public_key: sr25519::Pair::from_seed(&DAVE_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&DAVE_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 			DIdentity::Private(PrivateDid {
@@ -199,7 +245,17 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			}),
 			DIdentity::Private(PrivateDid {
 				identifier: EVE,
-				public_key: sr25519::Pair::from_seed(&EVE_SEED).public(),
+				public_key: sr25519::Pair::from_seed(&EVE_SEED).public().into(),
+				metadata: Default::default(),
+			}),
+			DIdentity::Private(PrivateDid {
+				identifier: FERDIE,
+				public_key: ed25519::Pair::from_seed(&FERDIE_SEED).public().into(),
+				metadata: Default::default(),
+			}),
+			DIdentity::Private(PrivateDid {
+				identifier: CHARLIE,
+				public_key: ecdsa::Pair::from_seed(&CHARLIE_SEED).public().into(),
 				metadata: Default::default(),
 			}),
 		],
@@ -215,7 +271,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	}
 	.assimilate_storage(&mut o)
 	.unwrap();
-	o.into()
+
+	let mut ext: sp_io::TestExternalities = o.into();
+	// keys of the benchmark issuers are generated in the keystore
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+	ext
 }
 
 
@@ -1,9 +1,11 @@
 use super::*;
-use crate::mock::{VC,  *};
-use sp_core::{sr25519, Pair, H256};
-use metamui_primitives::types::{ TokenVC, VC as VCStruct};
+use crate::mock::{Event, VC,  *};
+use sp_core::{ecdsa, ed25519, sr25519, Pair, H256};
+use metamui_primitives::{ traits::VCResolve, types::{ TokenVC, VCExtension, VCv2, VersionedVC, VC as VCStruct} };
 use frame_support::{
 	assert_noop, assert_ok,
+	traits::Hooks,
+	BoundedVec,
 };
 
 #[test]
@@ -15,6 +17,7 @@ fn test_store() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -22,14 +25,15 @@ fn test_store() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -55,6 +59,7 @@ fn test_invalid_owner_vc() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -62,14 +67,15 @@ fn test_invalid_owner_vc() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -91,13 +97,14 @@ fn test_invalid_owner_vc() {
 		let mint_vc = SlashMintTokens { vc_id, currency_code, amount: 1000 };
 		let mint_vc: [u8; 128] = convert_to_array::<128>(mint_vc.encode());
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: mint_vc,
@@ -119,6 +126,7 @@ fn test_mint_vc_store() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code,
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -126,14 +134,15 @@ fn test_mint_vc_store() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -154,13 +163,14 @@ fn test_mint_vc_store() {
 		let mint_vc = SlashMintTokens { vc_id, currency_code, amount: 1000 };
 		let mint_vc: [u8; 128] = convert_to_array::<128>(mint_vc.encode());
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: mint_vc,
@@ -186,6 +196,7 @@ fn test_cccode_validation() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTHs".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -193,14 +204,15 @@ fn test_cccode_validation() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers: issuers.clone(),
 			signatures: vec![signature.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -215,6 +227,7 @@ fn test_cccode_validation() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>(" OT H".into()),
+			max_supply: None,
 		};
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
 
@@ -224,7 +237,8 @@ fn test_cccode_validation() {
 			vc_type: vc_type.clone(),
 			owner,
 			issuers: issuers.clone(),
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			vc_property: token_vc,
 			is_vc_active: true,
 		};
@@ -238,6 +252,7 @@ fn test_cccode_validation() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("1OTH".into()),
+			max_supply: None,
 		};
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
 
@@ -246,7 +261,8 @@ fn test_cccode_validation() {
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -268,6 +284,7 @@ fn test_update_status() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -275,14 +292,15 @@ fn test_update_status() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -308,6 +326,7 @@ fn test_store_vc_with_different_account() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -315,14 +334,15 @@ fn test_store_vc_with_different_account() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -344,20 +364,22 @@ fn test_store_vc_with_wrong_hash() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
 		let vc_type = VCType::TokenVC;
 		// Wrong Hash
 		let hash = H256::zero();
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner: BOB,
 			issuers: vec![BOB],
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -379,6 +401,7 @@ fn test_store_vc_with_wrong_signature() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -387,14 +410,15 @@ fn test_store_vc_with_wrong_signature() {
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
 		let wrong_hash = H256::zero();
-		let signature = pair.sign(wrong_hash.as_ref());
+		let signature: MultiSignature = pair.sign(wrong_hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -416,6 +440,7 @@ fn test_store_vc_less_approvers() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -424,14 +449,15 @@ fn test_store_vc_less_approvers() {
 		let issuers = vec![BOB, DAVE];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
 		let wrong_hash = H256::zero();
-		let signature = pair.sign(wrong_hash.as_ref());
+		let signature: MultiSignature = pair.sign(wrong_hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -453,6 +479,7 @@ fn test_update_status_sender() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -460,14 +487,15 @@ fn test_update_status_sender() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -502,6 +530,7 @@ fn test_add_signature() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -509,16 +538,17 @@ fn test_add_signature() {
 		let owner = BOB;
 		let issuers = vec![BOB, DAVE, EVE];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let bob_sign = bob_pair.sign(hash.as_ref());
-		let dave_sign = dave_pair.sign(hash.as_ref());
-		let eve_sign = eve_pair.sign(hash.as_ref());
+		let bob_sign: MultiSignature = bob_pair.sign(hash.as_ref()).into();
+		let dave_sign: MultiSignature = dave_pair.sign(hash.as_ref()).into();
+		let eve_sign: MultiSignature = eve_pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![bob_sign.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -538,7 +568,8 @@ fn test_add_signature() {
 			vc_type: vc_type.clone(),
 			owner: BOB,
 			issuers: vec![BOB, DAVE, EVE],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			vc_property: token_vc,
 			is_vc_active: false,
 		};
@@ -554,7 +585,8 @@ fn test_add_signature() {
 			owner: BOB,
 			issuers: vec![BOB, DAVE, EVE],
 			signatures: vec![bob_sign, dave_sign, eve_sign.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -579,6 +611,7 @@ fn test_add_signature_with_one_of_the_signers() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -586,18 +619,19 @@ fn test_add_signature_with_one_of_the_signers() {
 		let owner = BOB;
 		let issuers = vec![BOB, DAVE, EVE];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let bob_sign = bob_pair.sign(hash.as_ref());
+		let bob_sign: MultiSignature = bob_pair.sign(hash.as_ref()).into();
 		// signed by Dave's public key
-		let dave_sign = dave_pair.sign(hash.as_ref());
+		let dave_sign: MultiSignature = dave_pair.sign(hash.as_ref()).into();
 		// signed by Eve's public key
-		let eve_sign = eve_pair.sign(hash.as_ref());
+		let eve_sign: MultiSignature = eve_pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![bob_sign.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -617,7 +651,8 @@ fn test_add_signature_with_one_of_the_signers() {
 			vc_type: vc_type.clone(),
 			owner: BOB,
 			issuers: vec![BOB, DAVE, EVE],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			vc_property: token_vc,
 			is_vc_active: false,
 		};
@@ -633,7 +668,8 @@ fn test_add_signature_with_one_of_the_signers() {
 			owner: BOB,
 			issuers: vec![BOB, DAVE, EVE],
 			signatures: vec![bob_sign, dave_sign, eve_sign.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: token_vc,
@@ -647,7 +683,7 @@ fn test_add_signature_with_one_of_the_signers() {
 }
 
 #[test]
-fn test_set_is_used_flag() {
+fn test_consume_vc() {
 	new_test_ext().execute_with(|| {
 		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
 
@@ -656,6 +692,7 @@ fn test_set_is_used_flag() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -663,14 +700,15 @@ fn test_set_is_used_flag() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: false,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -680,10 +718,99 @@ fn test_set_is_used_flag() {
 
 		let vc_id = Lookup::<Test>::get(&BOB)[0];
 
-		// set vc is_used flag as true
-		VC::set_is_used_flag(vc_id, Some(true));
+		// consume the only use of the vc
+		assert_eq!(VC::consume(vc_id), Ok(Some(0)));
 		let vc_details = VCs::<Test>::get(vc_id).unwrap();
-		assert!(vc_details.is_vc_used);
+		assert!(vc_details.is_vc_used());
+
+		assert_noop!(VC::consume(vc_id), Error::<Test>::VCAlreadyUsed);
+	})
+}
+
+#[test]
+fn test_consume_multi_use_vc() {
+	new_test_ext().execute_with(|| {
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let max_uses = Some(3);
+		// max_uses is part of the signed hash for multi use vcs
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers, &max_uses));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses,
+			used_count: 0,
+			is_vc_active: true,
+			vc_type,
+			vc_property: token_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+
+		let vc_id = Lookup::<Test>::get(&BOB)[0];
+
+		assert_eq!(VC::consume(vc_id), Ok(Some(2)));
+		assert_eq!(VC::consume(vc_id), Ok(Some(1)));
+		assert_eq!(VC::consume(vc_id), Ok(Some(0)));
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().used_count, 3);
+		assert_noop!(VC::consume(vc_id), Error::<Test>::VCAlreadyUsed);
+	})
+}
+
+#[test]
+fn test_max_uses_not_signed() {
+	new_test_ext().execute_with(|| {
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		// Hash signed for a single use vc
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		// Submitter tries to make the vc usable forever
+		let vc: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: None,
+			used_count: 0,
+			is_vc_active: true,
+			vc_type,
+			vc_property: token_vc,
+		};
+
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()),
+			Error::<Test>::VCPropertiesNotVerified
+		);
 	})
 }
 
@@ -697,6 +824,7 @@ fn test_duplicate_issuers_signatures() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		// case when duplicate signatures are present
@@ -705,14 +833,15 @@ fn test_duplicate_issuers_signatures() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature.clone(), signature.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -727,14 +856,15 @@ fn test_duplicate_issuers_signatures() {
 		let owner = BOB;
 		let issuers = vec![BOB, BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -758,6 +888,7 @@ fn test_add_duplicate_issuer_signatures() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		// case when duplicate signatures are present
@@ -766,15 +897,16 @@ fn test_add_duplicate_issuer_signatures() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
-		let duplicate_signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+		let duplicate_signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers: issuers.clone(),
 			signatures: vec![signature.clone(), duplicate_signature.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -784,14 +916,15 @@ fn test_add_duplicate_issuer_signatures() {
 		Error::<Test>::DuplicateSignature
 	);
 
-		let dave_sign = dave_pair.sign(hash.as_ref());
+		let dave_sign: MultiSignature = dave_pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers: issuers.clone(),
 			signatures: vec![signature.clone(), dave_sign],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -806,7 +939,8 @@ fn test_add_duplicate_issuer_signatures() {
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type,
 			vc_property: token_vc,
@@ -827,24 +961,26 @@ fn test_generic_vc_store() {
 	new_test_ext().execute_with(|| {
 		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
 
-		let generic_vc = GenericVC { cid: convert_to_array::<64>("F0TAeD_UY2mK-agbzZTW".into()) };
+		let generic_vc = GenericVC {
+			cid: convert_to_array::<64>("QmdQYGSHZDGk6jDwCP2Sy4ybFAdH5PUvbt1oCcZzS1GucE".into()),
+		};
 
 		let generic_vc: [u8; 128] = convert_to_array::<128>(generic_vc.encode());
 
 		let vc_type = VCType::GenericVC;
 		let owner = BOB;
 		let issuers = vec![BOB];
-		// Hash for generic vc will be generated using
-		// the data stored in vc_url of generic_vc
-		let hash = BlakeTwo256::hash_of(&generic_vc);
-		let signature = pair.sign(hash.as_ref());
+		// the CID of the document is bound to the hash like any other property
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &generic_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![signature],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: true,
 			vc_type,
 			vc_property: generic_vc,
@@ -863,6 +999,58 @@ fn test_generic_vc_store() {
 		assert_eq!(Lookup::<Test>::get(did), vec![vc_id]);
 		assert_eq!(VCs::<Test>::get(vc_id), Some(vc.clone()));
 		assert_eq!(VCHistory::<Test>::get(vc_id), Some((vc.is_vc_active, 0)));
+
+		let cid = VC::document_cid(vc_id).unwrap();
+		assert_eq!((cid.version, cid.hash_code), (0, 0x12));
+		assert_eq!(cid.digest, sp_core::hashing::sha2_256(b"metamui document").to_vec());
+	})
+}
+
+#[test]
+fn test_generic_vc_cid_validation() {
+	new_test_ext().execute_with(|| {
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let vc_type = VCType::GenericVC;
+
+		let build = |cid: &str| {
+			let generic_vc = GenericVC { cid: convert_to_array::<64>(cid.into()) };
+			let vc_property: [u8; 128] = convert_to_array::<128>(generic_vc.encode());
+			let hash = BlakeTwo256::hash_of(&(&vc_type, &vc_property, &owner, &issuers));
+			let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+			VCStruct {
+				hash,
+				owner,
+				issuers: issuers.clone(),
+				signatures: vec![signature],
+				max_uses: Some(1),
+				used_count: 0,
+				is_vc_active: true,
+				vc_type: vc_type.clone(),
+				vc_property,
+			}
+		};
+
+		// not a CID
+		let vc: VCStruct<H256> = build("F0TAeD_UY2mK-agbzZTW");
+		assert_noop!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()), Error::<Test>::InvalidCid);
+
+		// the signed hash is for another document
+		let mut vc: VCStruct<H256> = build("bafkreig73zl2zl2iyiurn6dyobqou7vw2eughkg2d6plzxwhmtsemzcvqm");
+		let other_cid = GenericVC {
+			cid: convert_to_array::<64>("QmdQYGSHZDGk6jDwCP2Sy4ybFAdH5PUvbt1oCcZzS1GucE".into()),
+		};
+		vc.vc_property = convert_to_array::<128>(other_cid.encode());
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()),
+			Error::<Test>::VCPropertiesNotVerified
+		);
+
+		let vc: VCStruct<H256> = build("bafkreig73zl2zl2iyiurn6dyobqou7vw2eughkg2d6plzxwhmtsemzcvqm");
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		let vc_id = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+		assert_eq!(VC::document_cid(vc_id).map(|cid| (cid.version, cid.codec)), Some((1, 0x55)));
 	})
 }
 
@@ -876,6 +1064,7 @@ fn test_vc_already_exists() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -883,14 +1072,15 @@ fn test_vc_already_exists() {
 		let owner = BOB;
 		let issuers = vec![BOB];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let signature = pair.sign(hash.as_ref());
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers: issuers.clone(),
 			signatures: vec![signature.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -913,6 +1103,7 @@ fn test_invalid_signature_for_add_signature() {
 			reservable_balance: 1000,
 			decimal: 6,
 			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
 		};
 
 		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
@@ -920,15 +1111,16 @@ fn test_invalid_signature_for_add_signature() {
 		let owner = BOB;
 		let issuers = vec![DAVE];
 		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
-		let bob_sign = bob_pair.sign(hash.as_ref());
-		let dave_sign = dave_pair.sign(hash.as_ref());
+		let bob_sign: MultiSignature = bob_pair.sign(hash.as_ref()).into();
+		let dave_sign: MultiSignature = dave_pair.sign(hash.as_ref()).into();
 
 		let vc: VCStruct<H256> = VCStruct {
 			hash,
 			owner,
 			issuers,
 			signatures: vec![bob_sign.clone()],
-			is_vc_used: true,
+			max_uses: Some(1),
+			used_count: 0,
 			is_vc_active: false,
 			vc_type: vc_type.clone(),
 			vc_property: token_vc,
@@ -941,3 +1133,1077 @@ fn test_invalid_signature_for_add_signature() {
 		assert_noop!(VC::validate_sign(&vc, bob_sign.clone(), vc_id), Error::<Test>::InvalidSignature);
 	})
 }
+
+#[test]
+fn test_consume_amount() {
+	new_test_ext().execute_with(|| {
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let currency_code = convert_to_array::<8>("OTH".into());
+
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code,
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: true,
+			vc_type,
+			vc_property: token_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		let token_vc_id = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+
+		// TokenVC does not carry an amount
+		assert_noop!(VC::consume_amount(token_vc_id, 1), Error::<Test>::VCHasNoAmount);
+
+		let vc_type = VCType::MintTokens;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let max_uses = Some(2);
+		let mint_vc = SlashMintTokens { vc_id: token_vc_id, currency_code, amount: 1000 };
+		let mint_vc: [u8; 128] = convert_to_array::<128>(mint_vc.encode());
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &mint_vc, &owner, &issuers, &max_uses));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+		let vc: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses,
+			used_count: 0,
+			is_vc_active: true,
+			vc_type,
+			vc_property: mint_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		let vc_id = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+
+		assert_noop!(VC::consume_amount(vc_id, 0), Error::<Test>::InvalidAmount);
+		assert_noop!(VC::consume_amount(vc_id, 1001), Error::<Test>::AmountExceedsRemaining);
+
+		assert_eq!(VC::consume_amount(vc_id, 400), Ok(600));
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().used_count, 0);
+
+		// exhausting the amount consumes one use and starts the next one with the full amount
+		assert_eq!(VC::consume_amount(vc_id, 600), Ok(1000));
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().used_count, 1);
+
+		assert_eq!(VC::consume_amount(vc_id, 1000), Ok(0));
+		assert!(VCs::<Test>::get(vc_id).unwrap().is_vc_used());
+		assert_eq!(RemainingAmount::<Test>::get(vc_id), None);
+		assert_noop!(VC::consume_amount(vc_id, 1), Error::<Test>::VCAlreadyUsed);
+	})
+}
+
+#[test]
+fn test_store_vc_with_ed25519_and_ecdsa_issuers() {
+	new_test_ext().execute_with(|| {
+		let ferdie_pair: ed25519::Pair = ed25519::Pair::from_seed(&FERDIE_SEED);
+		let charlie_pair: ecdsa::Pair = ecdsa::Pair::from_seed(&CHARLIE_SEED);
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let owner = BOB;
+		let issuers = vec![FERDIE, CHARLIE];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let ferdie_sign: MultiSignature = ferdie_pair.sign(hash.as_ref()).into();
+		let charlie_sign: MultiSignature = charlie_pair.sign(hash.as_ref()).into();
+
+		let vc: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![ferdie_sign],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: false,
+			vc_type,
+			vc_property: token_vc,
+		};
+
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		let vc_id = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().is_vc_active, false);
+		assert_eq!(VCApproverList::<Test>::get(vc_id), vec![FERDIE]);
+
+		assert_ok!(VC::add_signature(Origin::signed(BOB_ACCOUNT_ID), vc_id, charlie_sign));
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().is_vc_active, true);
+		assert_eq!(VCApproverList::<Test>::get(vc_id), vec![FERDIE, CHARLIE]);
+	})
+}
+
+#[test]
+fn test_signature_scheme_mismatch() {
+	new_test_ext().execute_with(|| {
+		// BOB holds an sr25519 key, so an ed25519 signature from the same seed must be rejected
+		let pair: ed25519::Pair = ed25519::Pair::from_seed(&BOB_SEED);
+		let token_vc = TokenVC {
+			token_name: convert_to_array::<16>("test".into()),
+			reservable_balance: 1000,
+			decimal: 6,
+			currency_code: convert_to_array::<8>("OTH".into()),
+			max_supply: None,
+		};
+
+		let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+		let vc_type = VCType::TokenVC;
+		let owner = BOB;
+		let issuers = vec![BOB];
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+		let signature: MultiSignature = pair.sign(hash.as_ref()).into();
+
+		let vc: VCStruct<H256> = VCStruct {
+			hash,
+			owner,
+			issuers,
+			signatures: vec![signature],
+			max_uses: Some(1),
+			used_count: 0,
+			is_vc_active: true,
+			vc_type,
+			vc_property: token_vc,
+		};
+
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()),
+			Error::<Test>::InvalidSignature
+		);
+	})
+}
+
+fn signed_token_vc(token_name: &str, issuers: Vec<metamui_primitives::Did>, pairs: Vec<sr25519::Pair>) -> (VCid, VCStruct<H256>) {
+	let token_vc = TokenVC {
+		token_name: convert_to_array::<16>(token_name.into()),
+		reservable_balance: 1000,
+		decimal: 6,
+		currency_code: convert_to_array::<8>("OTH".into()),
+		max_supply: None,
+	};
+
+	let token_vc: [u8; 128] = convert_to_array::<128>(token_vc.encode());
+	let vc_type = VCType::TokenVC;
+	let owner = BOB;
+	let hash = BlakeTwo256::hash_of(&(&vc_type, &token_vc, &owner, &issuers));
+	let signatures: Vec<MultiSignature> =
+		pairs.iter().map(|pair| pair.sign(hash.as_ref()).into()).collect();
+
+	let vc: VCStruct<H256> = VCStruct {
+		hash,
+		owner,
+		issuers,
+		signatures,
+		max_uses: Some(1),
+		used_count: 0,
+		is_vc_active: true,
+		vc_type,
+		vc_property: token_vc,
+	};
+	(*BlakeTwo256::hash_of(&vc).as_fixed_bytes(), vc)
+}
+
+fn store_token_vc(issuers: Vec<metamui_primitives::Did>, pairs: Vec<sr25519::Pair>) -> VCid {
+	let (vc_id, vc) = signed_token_vc("test", issuers, pairs);
+	assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+	vc_id
+}
+
+#[test]
+fn test_transfer_with_issuers_consent() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let eve_pair: sr25519::Pair = sr25519::Pair::from_seed(&EVE_SEED);
+		let vc_id = store_token_vc(vec![BOB, EVE], vec![bob_pair, eve_pair]);
+
+		// only the owner can request the transfer
+		assert_noop!(
+			VC::request_transfer(Origin::signed(DAVE_ACCOUNT_ID), vc_id, DAVE),
+			Error::<Test>::NotVCOwner
+		);
+		assert_noop!(
+			VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, BOB),
+			Error::<Test>::AlreadyVCOwner
+		);
+		assert_noop!(
+			VC::approve_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		assert_ok!(VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE));
+		assert_eq!(
+			PendingTransfers::<Test>::get(vc_id),
+			Some(VCTransfer { new_owner: DAVE, approvals: vec![] })
+		);
+
+		// DAVE is not an issuer of the VC
+		assert_noop!(
+			VC::approve_transfer(Origin::signed(DAVE_ACCOUNT_ID), vc_id),
+			Error::<Test>::NotAnIssuer
+		);
+
+		assert_ok!(VC::approve_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+		assert_noop!(
+			VC::approve_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id),
+			Error::<Test>::DuplicateSignature
+		);
+		// not transferred until every issuer consented
+		assert_eq!(RLookup::<Test>::get(vc_id), BOB);
+
+		let eve_account_id = pallet_did::Pallet::<Test>::get_accountid_from_pubkey(
+			&sr25519::Pair::from_seed(&EVE_SEED).public().into(),
+		).unwrap();
+		assert_ok!(VC::approve_transfer(Origin::signed(eve_account_id), vc_id));
+
+		assert_eq!(RLookup::<Test>::get(vc_id), DAVE);
+		assert_eq!(VC::vc_owner(&vc_id), Some(DAVE));
+		assert_eq!(Lookup::<Test>::get(DAVE), vec![vc_id]);
+		assert!(!Lookup::<Test>::contains_key(BOB));
+		assert_eq!(PendingTransfers::<Test>::get(vc_id), None);
+		// the VC itself is unchanged, so the issuers' signatures stay valid
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().owner, BOB);
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().is_vc_active, true);
+	})
+}
+
+#[test]
+fn test_force_approve_transfer() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let vc_id = store_token_vc(vec![BOB], vec![bob_pair]);
+
+		assert_noop!(VC::force_approve_transfer(Origin::root(), vc_id), Error::<Test>::NoPendingTransfer);
+
+		assert_ok!(VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE));
+		assert_noop!(VC::force_approve_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id), DispatchError::BadOrigin);
+		assert_ok!(VC::force_approve_transfer(Origin::root(), vc_id));
+
+		assert_eq!(RLookup::<Test>::get(vc_id), DAVE);
+		assert_eq!(Lookup::<Test>::get(DAVE), vec![vc_id]);
+
+		// the previous owner has lost control over the VC
+		assert_noop!(
+			VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, BOB),
+			Error::<Test>::NotVCOwner
+		);
+	})
+}
+
+#[test]
+fn test_cancel_transfer() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let vc_id = store_token_vc(vec![BOB], vec![bob_pair]);
+
+		assert_ok!(VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, DAVE));
+		// the pending transfer is not replaced by a new request
+		assert_noop!(
+			VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, EVE),
+			Error::<Test>::TransferPending
+		);
+		assert_ok!(VC::cancel_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+		assert_eq!(PendingTransfers::<Test>::get(vc_id), None);
+		assert_ok!(VC::request_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id, EVE));
+		assert_ok!(VC::cancel_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id));
+
+		assert_noop!(VC::force_approve_transfer(Origin::root(), vc_id), Error::<Test>::NoPendingTransfer);
+		assert_noop!(
+			VC::cancel_transfer(Origin::signed(BOB_ACCOUNT_ID), vc_id),
+			Error::<Test>::NoPendingTransfer
+		);
+		assert_eq!(RLookup::<Test>::get(vc_id), BOB);
+	})
+}
+
+#[test]
+fn test_query_vcs() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let vc_id = store_token_vc(vec![BOB, DAVE, EVE], vec![bob_pair.clone()]);
+		let other_vc_id = store_token_vc(vec![BOB], vec![bob_pair]);
+
+		assert_eq!(IssuerLookup::<Test>::get(BOB), vec![vc_id, other_vc_id]);
+		assert_eq!(IssuerLookup::<Test>::get(DAVE), vec![vc_id]);
+
+		let owned: Vec<VCid> = VC::vcs_of_owner(BOB, 0, 10).into_iter().map(|(id, _)| id).collect();
+		assert_eq!(owned, vec![vc_id, other_vc_id]);
+		let owned: Vec<VCid> = VC::vcs_of_owner(BOB, 1, 10).into_iter().map(|(id, _)| id).collect();
+		assert_eq!(owned, vec![other_vc_id]);
+		assert_eq!(VC::vcs_of_owner(BOB, 0, 1).len(), 1);
+		assert_eq!(VC::vcs_issued_by(EVE, 0, 10), vec![(vc_id, VCs::<Test>::get(vc_id).unwrap())]);
+		assert!(VC::vcs_issued_by(EVE, 1, 10).is_empty());
+
+		assert_eq!(VC::pending_signatures(vc_id), vec![DAVE, EVE]);
+		assert!(VC::pending_signatures(other_vc_id).is_empty());
+
+		assert_eq!(
+			VC::vc_status(vc_id),
+			Some(VCStatus {
+				owner: BOB,
+				is_vc_active: false,
+				is_vc_used: false,
+				used_count: 0,
+				max_uses: Some(1),
+			})
+		);
+		assert_eq!(VC::vc_status([0; 32]), None);
+	})
+}
+
+#[test]
+fn test_supersede_vc() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let old_vc_id = store_token_vc(vec![BOB], vec![bob_pair.clone()]);
+
+		let (new_vc_id, new_vc) = signed_token_vc("fixed", vec![BOB], vec![bob_pair.clone()]);
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, new_vc.encode()));
+
+		assert_eq!(Supersedes::<Test>::get(new_vc_id), Some(old_vc_id));
+		assert_eq!(SupersededBy::<Test>::get(old_vc_id), Some(new_vc_id));
+		assert_eq!(VCs::<Test>::get(old_vc_id).unwrap().is_vc_active, false);
+		assert_eq!(VCs::<Test>::get(new_vc_id).unwrap().is_vc_active, true);
+
+		// resolving the old VC returns the latest version
+		assert_eq!(<VC as VCResolve<H256>>::get_vc(&old_vc_id), VCs::<Test>::get(new_vc_id));
+		assert_eq!(VC::latest_vc_id(&old_vc_id), new_vc_id);
+
+		// the old VC can not be activated or replaced again
+		assert_noop!(
+			VC::update_status(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, true),
+			Error::<Test>::VCAlreadySuperseded
+		);
+		let (latest_vc_id, other_vc) = signed_token_vc("other", vec![BOB], vec![bob_pair.clone()]);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, other_vc.encode()),
+			Error::<Test>::VCAlreadySuperseded
+		);
+
+		// the chain is followed to the latest version
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), new_vc_id, other_vc.encode()));
+		assert_eq!(VC::latest_vc_id(&old_vc_id), latest_vc_id);
+		assert_eq!(<VC as VCResolve<H256>>::get_vc(&old_vc_id), VCs::<Test>::get(latest_vc_id));
+
+		// the chain holds at most MaxSupersedeDepth replacements
+		assert_eq!(SupersedeDepth::<Test>::get(latest_vc_id), 2);
+		let (_, third_vc) = signed_token_vc("third", vec![BOB], vec![bob_pair]);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), latest_vc_id, third_vc.encode()),
+			Error::<Test>::SupersedeChainTooLong
+		);
+	})
+}
+
+#[test]
+fn test_supersede_waits_for_signatures() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let dave_pair: sr25519::Pair = sr25519::Pair::from_seed(&DAVE_SEED);
+		let old_vc_id = store_token_vc(vec![BOB], vec![bob_pair.clone()]);
+
+		let (new_vc_id, new_vc) = signed_token_vc("fixed", vec![BOB, DAVE], vec![bob_pair.clone()]);
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, new_vc.encode()));
+		assert_eq!(PendingSupersede::<Test>::get(old_vc_id), Some(new_vc_id));
+
+		// the old VC stays in use until the new version is active
+		assert_eq!(SupersededBy::<Test>::get(old_vc_id), None);
+		assert_eq!(VCs::<Test>::get(old_vc_id).unwrap().is_vc_active, true);
+		assert_eq!(VC::latest_vc_id(&old_vc_id), old_vc_id);
+
+		// no other version can race the pending one
+		let (_, other_vc) = signed_token_vc("other", vec![BOB, DAVE], vec![bob_pair]);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, other_vc.encode()),
+			Error::<Test>::SupersedePending
+		);
+
+		let dave_sign: MultiSignature = dave_pair.sign(new_vc.hash.as_ref()).into();
+		assert_ok!(VC::add_signature(Origin::signed(DAVE_ACCOUNT_ID), new_vc_id, dave_sign));
+
+		assert_eq!(PendingSupersede::<Test>::get(old_vc_id), None);
+		assert_eq!(SupersededBy::<Test>::get(old_vc_id), Some(new_vc_id));
+		assert_eq!(VCs::<Test>::get(old_vc_id).unwrap().is_vc_active, false);
+		assert_eq!(VC::latest_vc_id(&old_vc_id), new_vc_id);
+	})
+}
+
+#[test]
+fn test_abandoned_supersede() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let old_vc_id = store_token_vc(vec![BOB], vec![bob_pair.clone()]);
+
+		let (new_vc_id, new_vc) = signed_token_vc("fixed", vec![BOB, DAVE], vec![bob_pair.clone()]);
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, new_vc.encode()));
+
+		// the owner abandons the pending version, the VC can be superseded once it is pruned
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), new_vc_id, false));
+		let (other_vc_id, other_vc) = signed_token_vc("other", vec![BOB], vec![bob_pair]);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, other_vc.encode()),
+			Error::<Test>::SupersedePending
+		);
+		VC::on_idle(11, Weight::max_value());
+		assert!(PrunedVCs::<Test>::contains_key(new_vc_id));
+		assert_eq!(PendingSupersede::<Test>::get(old_vc_id), None);
+
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, other_vc.encode()));
+		assert_eq!(VC::latest_vc_id(&old_vc_id), other_vc_id);
+	})
+}
+
+#[test]
+fn test_supersede_checks() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let old_vc_id = store_token_vc(vec![BOB], vec![bob_pair.clone()]);
+
+		let (_, new_vc) = signed_token_vc("fixed", vec![BOB], vec![bob_pair.clone()]);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), [0; 32], new_vc.encode()),
+			Error::<Test>::VCIdDoesNotExist
+		);
+
+		// the new version must have the same type
+		let generic_vc: VCStruct<H256> = VCStruct { vc_type: VCType::GenericVC, ..new_vc.clone() };
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, generic_vc.encode()),
+			Error::<Test>::VCTypeMismatch
+		);
+
+		// the new version must be owned by the owner of the replaced VC
+		let dave_vc: VCStruct<H256> = VCStruct { owner: DAVE, ..new_vc };
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, dave_vc.encode()),
+			Error::<Test>::NotVCOwner
+		);
+	})
+}
+
+#[test]
+fn test_supersede_needs_every_issuer() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let eve_pair: sr25519::Pair = sr25519::Pair::from_seed(&EVE_SEED);
+		let old_vc_id = store_token_vc(vec![BOB, EVE], vec![bob_pair.clone(), eve_pair]);
+
+		// a single issuer can not replace, and so revoke, a VC issued with others
+		let (_, bob_vc) = signed_token_vc("fixed", vec![BOB], vec![bob_pair.clone()]);
+		assert_noop!(
+			VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, bob_vc.encode()),
+			Error::<Test>::MissingIssuers
+		);
+		assert_eq!(VCs::<Test>::get(old_vc_id).unwrap().is_vc_active, true);
+
+		// new issuers can be added, the new version waits for their signatures
+		let (new_vc_id, new_vc) =
+			signed_token_vc("fixed", vec![BOB, EVE, DAVE], vec![bob_pair]);
+		assert_ok!(VC::supersede(Origin::signed(BOB_ACCOUNT_ID), old_vc_id, new_vc.encode()));
+		assert_eq!(Supersedes::<Test>::get(new_vc_id), Some(old_vc_id));
+		assert_eq!(SupersededBy::<Test>::get(old_vc_id), None);
+	})
+}
+
+#[test]
+fn test_prune_retired_vcs() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let (vc_id, vc) = signed_token_vc("test", vec![BOB], vec![bob_pair]);
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		assert_ok!(VC::consume(vc_id));
+		assert_eq!(RetiredAt::<Test>::get(vc_id), Some(1));
+
+		// the VC is kept during the retention period
+		VC::on_idle(10, Weight::max_value());
+		assert!(VCs::<Test>::contains_key(vc_id));
+
+		VC::on_idle(11, Weight::max_value());
+		assert_eq!(VCs::<Test>::get(vc_id), None);
+		assert!(!RLookup::<Test>::contains_key(vc_id));
+		assert!(!Lookup::<Test>::get(BOB).contains(&vc_id));
+		assert!(!IssuerLookup::<Test>::get(BOB).contains(&vc_id));
+		assert_eq!(VCHistory::<Test>::get(vc_id), None);
+		assert_eq!(VCApproverList::<Test>::get(vc_id), Vec::<metamui_primitives::Did>::new());
+		assert_eq!(RetiredAt::<Test>::get(vc_id), None);
+		assert!(PrunedVCs::<Test>::contains_key(vc_id));
+		assert!(<VC as VCResolve<H256>>::is_vc_used(&vc_id));
+		System::assert_last_event(Event::VC(crate::Event::VCPruned { vcid: vc_id }));
+
+		// a pruned VC can not be stored again
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()),
+			Error::<Test>::VCAlreadyExists
+		);
+	})
+}
+
+#[test]
+fn test_reactivated_vc_is_not_pruned() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let vc_id = store_token_vc(vec![BOB], vec![bob_pair]);
+
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, false));
+		assert_eq!(RetiredAt::<Test>::get(vc_id), Some(1));
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, true));
+		assert_eq!(RetiredAt::<Test>::get(vc_id), None);
+		assert!(PruneQueue::<Test>::get(1).is_empty());
+
+		VC::on_idle(11, Weight::max_value());
+		assert!(VCs::<Test>::contains_key(vc_id));
+		assert!(!PrunedVCs::<Test>::contains_key(vc_id));
+
+		// revoking it again restarts the retention period, once
+		System::set_block_number(5);
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, false));
+		System::set_block_number(6);
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, false));
+		assert_eq!(PruneQueue::<Test>::get(5).into_inner(), vec![vc_id]);
+		assert!(PruneQueue::<Test>::get(6).is_empty());
+		assert_eq!(RetiredAt::<Test>::get(vc_id), Some(5));
+		VC::on_idle(14, Weight::max_value());
+		assert!(VCs::<Test>::contains_key(vc_id));
+		VC::on_idle(15, Weight::max_value());
+		assert!(PrunedVCs::<Test>::contains_key(vc_id));
+	})
+}
+
+#[test]
+fn test_pruning_is_bounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let vc_ids: Vec<VCid> = ["one", "two", "three"]
+			.iter()
+			.map(|name| {
+				let (vc_id, vc) = signed_token_vc(name, vec![BOB], vec![bob_pair.clone()]);
+				assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+				assert_ok!(VC::consume(vc_id));
+				vc_id
+			})
+			.collect();
+
+		// at most MaxPrunedPerBlock VCs are pruned at once, the empty block 0 counts as one
+		VC::on_idle(11, Weight::max_value());
+		assert_eq!(vc_ids.iter().filter(|vc_id| PrunedVCs::<Test>::contains_key(vc_id)).count(), 1);
+		assert_eq!(PruneCursor::<Test>::get(), 1);
+
+		VC::on_idle(12, Weight::max_value());
+		assert!(vc_ids.iter().all(|vc_id| PrunedVCs::<Test>::contains_key(vc_id)));
+		assert!(PruneQueue::<Test>::get(1).is_empty());
+		assert_eq!(PruneCursor::<Test>::get(), 2);
+
+		// walking empty blocks is bounded too
+		VC::on_idle(100, Weight::max_value());
+		assert_eq!(PruneCursor::<Test>::get(), 4);
+	})
+}
+
+#[test]
+fn test_prune_queue_is_bounded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let vc_ids: Vec<VCid> = ["one", "two", "three", "four"]
+			.iter()
+			.map(|name| {
+				let (vc_id, vc) = signed_token_vc(name, vec![BOB], vec![bob_pair.clone()]);
+				assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+				assert_ok!(VC::consume(vc_id));
+				vc_id
+			})
+			.collect();
+
+		// the queue of a block holds at most MaxRetiredPerBlock VCs, the rest is queued later
+		assert_eq!(PruneQueue::<Test>::get(1).into_inner(), vc_ids[..3].to_vec());
+		assert_eq!(PruneQueue::<Test>::get(2).into_inner(), vec![vc_ids[3]]);
+		assert_eq!(RetiredAt::<Test>::get(vc_ids[3]), Some(2));
+
+		PruneCursor::<Test>::put(1);
+		VC::on_idle(11, Weight::max_value());
+		VC::on_idle(11, Weight::max_value());
+		assert!(!PrunedVCs::<Test>::contains_key(vc_ids[3]));
+		VC::on_idle(12, Weight::max_value());
+		assert!(vc_ids.iter().all(|vc_id| PrunedVCs::<Test>::contains_key(vc_id)));
+	})
+}
+
+#[test]
+fn test_retire_fails_when_prune_queue_is_full() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let (vc_id, vc) = signed_token_vc("test", vec![BOB], vec![bob_pair]);
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+
+		// only MAX_RETIRE_SCAN blocks are searched for room
+		for block in 1..=MAX_RETIRE_SCAN as u64 {
+			let full: BoundedVec<VCid, <Test as crate::Config>::MaxRetiredPerBlock> =
+				vec![[0; 32]; 3].try_into().unwrap();
+			PruneQueue::<Test>::insert(block, full);
+		}
+		assert_noop!(VC::consume(vc_id), Error::<Test>::PruneQueueFull);
+		assert_noop!(
+			VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, false),
+			Error::<Test>::PruneQueueFull
+		);
+
+		PruneQueue::<Test>::remove(MAX_RETIRE_SCAN as u64);
+		assert_ok!(VC::consume(vc_id));
+		assert_eq!(RetiredAt::<Test>::get(vc_id), Some(MAX_RETIRE_SCAN as u64));
+	})
+}
+
+#[test]
+fn test_add_signatures_in_batch() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let dave_pair: sr25519::Pair = sr25519::Pair::from_seed(&DAVE_SEED);
+
+		let (first_vc_id, first_vc) = signed_token_vc("first", vec![BOB, DAVE], vec![bob_pair.clone()]);
+		let (second_vc_id, second_vc) = signed_token_vc("second", vec![BOB, DAVE], vec![bob_pair.clone()]);
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), first_vc.encode()));
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), second_vc.encode()));
+
+		let first_sign: MultiSignature = dave_pair.sign(first_vc.hash.as_ref()).into();
+		// signed over the wrong hash
+		let wrong_sign: MultiSignature = dave_pair.sign(first_vc.hash.as_ref()).into();
+		let second_sign: MultiSignature = dave_pair.sign(second_vc.hash.as_ref()).into();
+		let signatures = vec![
+			(first_vc_id, first_sign),
+			(second_vc_id, wrong_sign),
+			([0; 32], second_sign.clone()),
+		];
+		assert_ok!(VC::add_signatures(Origin::signed(DAVE_ACCOUNT_ID), signatures.try_into().unwrap()));
+
+		assert_eq!(VCs::<Test>::get(first_vc_id).unwrap().is_vc_active, true);
+		assert_eq!(VCs::<Test>::get(second_vc_id).unwrap().is_vc_active, false);
+		assert_eq!(VCApproverList::<Test>::get(second_vc_id), vec![BOB]);
+		System::assert_has_event(Event::VC(crate::Event::VCStatusUpdated {
+			vcid: first_vc_id,
+			vcstatus: true,
+		}));
+		System::assert_last_event(Event::VC(crate::Event::SignaturesAdded {
+			signed: vec![first_vc_id],
+			failed: vec![
+				(second_vc_id, Error::<Test>::InvalidSignature.into()),
+				([0; 32], Error::<Test>::VCIdDoesNotExist.into()),
+			],
+		}));
+
+		// the batch can be retried with the skipped signatures
+		let signatures = vec![(second_vc_id, second_sign)];
+		assert_ok!(VC::add_signatures(Origin::signed(DAVE_ACCOUNT_ID), signatures.try_into().unwrap()));
+		assert_eq!(VCs::<Test>::get(second_vc_id).unwrap().is_vc_active, true);
+	})
+}
+
+#[test]
+fn test_verify_presentation() {
+	new_test_ext().execute_with(|| {
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let dave_pair: sr25519::Pair = sr25519::Pair::from_seed(&DAVE_SEED);
+		let ferdie_pair: ed25519::Pair = ed25519::Pair::from_seed(&FERDIE_SEED);
+		let vc_id = store_token_vc(vec![BOB], vec![bob_pair.clone()]);
+		let challenge = b"login to portal, nonce 42";
+		let payload = VC::presentation_payload(&vc_id, challenge);
+		assert_eq!(&payload[..23], b"metamui/vc-presentation");
+		assert_eq!(&payload[23..55], &vc_id[..]);
+
+		let owner_signature: MultiSignature = bob_pair.sign(&payload).into();
+		assert_eq!(
+			VC::verify_presentation(vc_id, challenge, &owner_signature),
+			Some(PresentationVerdict {
+				owner: BOB,
+				is_vc_active: true,
+				is_vc_used: false,
+				is_revoked: false,
+				is_owner_key_match: true,
+				is_signature_valid: true,
+			})
+		);
+
+		// signed over another challenge
+		let verdict = VC::verify_presentation(vc_id, b"other challenge", &owner_signature).unwrap();
+		assert!(!verdict.is_signature_valid);
+
+		// signed over the bare challenge, without the presentation context
+		let bare_signature: MultiSignature = bob_pair.sign(challenge).into();
+		let verdict = VC::verify_presentation(vc_id, challenge, &bare_signature).unwrap();
+		assert!(!verdict.is_signature_valid);
+
+		// signed by someone else
+		let dave_signature: MultiSignature = dave_pair.sign(&payload).into();
+		let verdict = VC::verify_presentation(vc_id, challenge, &dave_signature).unwrap();
+		assert!(!verdict.is_signature_valid);
+
+		// signed with a key of another scheme
+		let ed25519_signature: MultiSignature = ferdie_pair.sign(&payload).into();
+		let verdict = VC::verify_presentation(vc_id, challenge, &ed25519_signature).unwrap();
+		assert!(!verdict.is_signature_valid);
+
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, false));
+		let verdict = VC::verify_presentation(vc_id, challenge, &owner_signature).unwrap();
+		assert!(!verdict.is_vc_active && verdict.is_revoked && verdict.is_signature_valid);
+
+		// the owner rotated the key the VC was stored with
+		let rotated_pair: sr25519::Pair = sr25519::Pair::from_seed(&[9; 32]);
+		assert_ok!(crate::mock::Did::rotate_key(
+			Origin::signed(VALIDATOR_ACCOUNT),
+			BOB,
+			PublicKey::Sr25519(rotated_pair.public()),
+			None
+		));
+		let verdict = VC::verify_presentation(vc_id, challenge, &owner_signature).unwrap();
+		assert!(!verdict.is_owner_key_match && !verdict.is_signature_valid);
+		let rotated_signature: MultiSignature = rotated_pair.sign(&payload).into();
+		let verdict = VC::verify_presentation(vc_id, challenge, &rotated_signature).unwrap();
+		assert!(!verdict.is_owner_key_match && verdict.is_signature_valid);
+
+		assert_eq!(VC::verify_presentation([0; 32], challenge, &owner_signature), None);
+	})
+}
+
+#[test]
+fn test_versioned_vc_envelope() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+
+		// a bare v1 VC and a v1 envelope are stored alike
+		let (vc_id, vc) = signed_token_vc("legacy", vec![BOB], vec![pair.clone()]);
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		let (vc_id_v1, vc) = signed_token_vc("envelope", vec![BOB], vec![pair.clone()]);
+		let envelope = VersionedVC::V1(vc).encode_envelope();
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), envelope));
+		assert!(VC::get_vc(&vc_id).unwrap().is_vc_active);
+		assert!(VC::get_vc(&vc_id_v1).unwrap().is_vc_active);
+
+		// the extension is signed along with the VC
+		let extension = VCExtension { expires_at: Some(10), schema: Some([7; 32]) };
+		let (_, mut vc) = signed_token_vc("expiring", vec![BOB], vec![]);
+		vc.hash = metamui_vc_verifier::hash_of_extended_vc::<BlakeTwo256>(&vc, &extension);
+		vc.signatures = vec![pair.sign(vc.hash.as_ref()).into()];
+		let vc_id = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+
+		// signed for the v1 format only
+		let (_, v1_vc) = signed_token_vc("expiring", vec![BOB], vec![pair.clone()]);
+		let envelope = VersionedVC::V2(VCv2 { vc: v1_vc, extension: extension.clone() }).encode_envelope();
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), envelope),
+			Error::<Test>::VCPropertiesNotVerified
+		);
+
+		// the extension does not match the signed one
+		let other_extension = VCExtension { expires_at: Some(100), ..extension.clone() };
+		let envelope = VersionedVC::V2(VCv2 { vc: vc.clone(), extension: other_extension }).encode_envelope();
+		assert_noop!(
+			VC::store(Origin::signed(BOB_ACCOUNT_ID), envelope),
+			Error::<Test>::VCPropertiesNotVerified
+		);
+
+		let envelope = VersionedVC::V2(VCv2 { vc, extension }).encode_envelope();
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), envelope));
+		assert!(VC::get_vc(&vc_id).unwrap().is_vc_active);
+
+		// usable up to the expiry block
+		System::set_block_number(10);
+		assert!(VC::get_vc(&vc_id).unwrap().is_vc_active);
+
+		System::set_block_number(11);
+		assert!(!VC::get_vc(&vc_id).unwrap().is_vc_active);
+		assert!(!VC::vc_status(vc_id).unwrap().is_vc_active);
+		assert_noop!(VC::consume(vc_id), Error::<Test>::VCExpired);
+
+		// v1 VCs do not expire
+		assert!(VC::get_vc(&vc_id_v1).unwrap().is_vc_active);
+		assert_ok!(VC::consume(vc_id_v1));
+
+		// an envelope of an unknown version is rejected
+		let mut envelope = metamui_primitives::types::VC_ENVELOPE_MAGIC.to_vec();
+		envelope.push(3);
+		assert_noop!(VC::store(Origin::signed(BOB_ACCOUNT_ID), envelope), Error::<Test>::InvalidVC);
+	})
+}
+
+#[test]
+fn test_v1_vc_with_envelope_prefix() {
+	new_test_ext().execute_with(|| {
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let (_, mut vc) = signed_token_vc("legacy", vec![BOB], vec![pair]);
+
+		// the hash of a v1 VC can start with the envelope prefix, it is still read as v1
+		let mut hash = vc.hash.to_fixed_bytes();
+		hash[..4].copy_from_slice(&metamui_primitives::types::VC_ENVELOPE_MAGIC);
+		hash[4] = 1;
+		vc.hash = H256::from(hash);
+		assert_eq!(
+			VersionedVC::<H256>::decode_envelope(&vc.encode()),
+			Ok(VersionedVC::V1(vc.clone()))
+		);
+
+		// an envelope of the VC is still read as an envelope
+		let envelope = VersionedVC::V1(vc.clone()).encode_envelope();
+		assert_eq!(VersionedVC::<H256>::decode_envelope(&envelope), Ok(VersionedVC::V1(vc)));
+	})
+}
+
+/// GenericVC owned by the given Did and signed by BOB
+fn signed_generic_vc(owner: metamui_primitives::Did, cid: &str) -> (VCid, VCStruct<H256>) {
+	let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+	let generic_vc = GenericVC { cid: convert_to_array::<64>(cid.into()) };
+	let vc = metamui_vc_verifier::build_vc::<BlakeTwo256>(
+		VCType::GenericVC,
+		convert_to_array::<128>(generic_vc.encode()),
+		owner,
+		vec![BOB],
+		Some(1),
+	);
+	let vc = VCStruct { signatures: vec![pair.sign(vc.hash.as_ref()).into()], ..vc };
+	(*BlakeTwo256::hash_of(&vc).as_fixed_bytes(), vc)
+}
+
+#[test]
+fn test_vc_storage_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bob_pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+
+		// TokenVCs stored through the approve origin pay no deposit
+		store_token_vc(vec![BOB], vec![bob_pair]);
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), 0);
+
+		let (vc_id, vc) = signed_generic_vc(DAVE, "QmdQYGSHZDGk6jDwCP2Sy4ybFAdH5PUvbt1oCcZzS1GucE");
+		// no extension, the option is encoded in one byte
+		let deposit = 10 + vc.encoded_size() as u64 + 1;
+		assert_eq!(VC::vc_deposit(vc.encoded_size() + 1), deposit);
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), deposit);
+		assert_eq!(Balances::free_balance(BOB_ACCOUNT_ID), INITIAL_BALANCE - deposit);
+		assert_eq!(VCDeposits::<Test>::get(vc_id), Some((BOB_ACCOUNT_ID, deposit)));
+
+		// deactivated by the issuer, the deposit is returned once the VC is pruned
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, false));
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), deposit);
+		VC::on_idle(11, Weight::max_value());
+		assert!(PrunedVCs::<Test>::contains_key(vc_id));
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), 0);
+		assert_eq!(VCDeposits::<Test>::get(vc_id), None);
+
+		// deactivated by the owner, the deposit stays reserved while the VC can be reactivated
+		System::set_block_number(2);
+		let (vc_id, vc) = signed_generic_vc(DAVE, "bafkreig73zl2zl2iyiurn6dyobqou7vw2eughkg2d6plzxwhmtsemzcvqm");
+		assert_ok!(VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()));
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), deposit);
+		assert_ok!(VC::update_status(Origin::signed(DAVE_ACCOUNT_ID), vc_id, false));
+		assert!(!VCs::<Test>::get(vc_id).unwrap().is_vc_active);
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), deposit);
+		assert_ok!(VC::update_status(Origin::signed(BOB_ACCOUNT_ID), vc_id, true));
+		assert!(VCs::<Test>::get(vc_id).unwrap().is_vc_active);
+		assert_eq!(VCDeposits::<Test>::get(vc_id), Some((BOB_ACCOUNT_ID, deposit)));
+
+		// and it is returned to the submitter once the VC is pruned
+		assert_ok!(VC::update_status(Origin::signed(DAVE_ACCOUNT_ID), vc_id, false));
+		VC::on_idle(12, Weight::max_value());
+		assert!(PrunedVCs::<Test>::contains_key(vc_id));
+		assert_eq!(Balances::reserved_balance(BOB_ACCOUNT_ID), 0);
+		assert_eq!(Balances::free_balance(BOB_ACCOUNT_ID), INITIAL_BALANCE);
+		System::assert_has_event(Event::VC(crate::Event::VCDepositReleased {
+			vcid: vc_id,
+			who: BOB_ACCOUNT_ID,
+			amount: deposit,
+		}));
+	})
+}
+
+mod proptests {
+	use super::*;
+	use proptest::prelude::*;
+
+	fn vc_type() -> impl Strategy<Value = VCType> {
+		prop_oneof![
+			Just(VCType::TokenVC),
+			Just(VCType::SlashTokens),
+			Just(VCType::MintTokens),
+			Just(VCType::TokenTransferVC),
+			Just(VCType::GenericVC),
+			Just(VCType::PublicDidVC),
+			Just(VCType::PrivateDidVC),
+		]
+	}
+
+	fn did() -> impl Strategy<Value = metamui_primitives::Did> {
+		prop_oneof![Just(ALICE), Just(BOB), Just(DAVE), Just(EVE), any::<[u8; 32]>()]
+	}
+
+	/// VC with arbitrary properties, signed by BOB when BOB is an issuer
+	fn arbitrary_vc() -> impl Strategy<Value = VCStruct<H256>> {
+		(
+			vc_type(),
+			prop::collection::vec(any::<u8>(), 128),
+			did(),
+			prop::collection::vec(did(), 0..4),
+			prop::option::of(0..4u32),
+		)
+			.prop_map(|(vc_type, vc_property, owner, issuers, max_uses)| {
+				let vc_property = convert_to_array::<128>(vc_property);
+				let mut vc = metamui_vc_verifier::build_vc::<BlakeTwo256>(
+					vc_type, vc_property, owner, issuers, max_uses,
+				);
+				if vc.issuers.contains(&BOB) {
+					let bob_pair = sr25519::Pair::from_seed(&BOB_SEED);
+					vc.signatures.push(bob_pair.sign(vc.hash.as_ref()).into());
+				}
+				vc
+			})
+	}
+
+	proptest! {
+		#[test]
+		fn decode_vc_never_panics(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+			new_test_ext().execute_with(|| {
+				if let Err(e) = VC::decode_vc::<VCStruct<H256>>(&bytes) {
+					prop_assert_eq!(e, Error::<Test>::InvalidVC.into());
+				}
+				let _ = VC::decode_vc::<TokenVC>(&bytes);
+				let _ = VC::decode_vc::<SlashMintTokens>(&bytes);
+				let _ = VC::decode_vc::<TokenTransferVC>(&bytes);
+				let _ = VC::decode_vc::<PublicDidVC>(&bytes);
+				let _ = VC::decode_vc::<PrivateDidVC>(&bytes);
+				Ok(())
+			})?;
+		}
+
+		#[test]
+		fn store_of_arbitrary_bytes_never_panics(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+			new_test_ext().execute_with(|| {
+				let _ = VC::store(Origin::signed(BOB_ACCOUNT_ID), bytes);
+			});
+		}
+
+		#[test]
+		fn store_of_arbitrary_vc_never_panics(vc in arbitrary_vc()) {
+			new_test_ext().execute_with(|| {
+				if VC::store(Origin::signed(BOB_ACCOUNT_ID), vc.encode()).is_ok() {
+					// a stored VC always matches its hash
+					let vc_id = *BlakeTwo256::hash_of(&vc).as_fixed_bytes();
+					let stored = VCs::<Test>::get(vc_id).unwrap();
+					prop_assert_eq!(VC::vc_hash(&stored), stored.hash);
+				}
+				Ok(())
+			})?;
+		}
+
+		#[test]
+		fn vc_hash_only_covers_signed_fields(
+			vc in arbitrary_vc(),
+			used_count in any::<u32>(),
+			is_vc_active in any::<bool>(),
+		) {
+			let mut changed = vc.clone();
+			changed.signatures.clear();
+			changed.used_count = used_count;
+			changed.is_vc_active = is_vc_active;
+			prop_assert_eq!(VC::vc_hash(&changed), VC::vc_hash(&vc));
+
+			changed.owner[0] = changed.owner[0].wrapping_add(1);
+			prop_assert_ne!(VC::vc_hash(&changed), VC::vc_hash(&vc));
+		}
+	}
+}
+
+#[test]
+fn test_legacy_did_vc_migration() {
+	new_test_ext().execute_with(|| {
+		let public_key = sr25519::Pair::from_seed(&EVE_SEED).public();
+		let vc_property: [u8; 128] = convert_to_array::<128>((public_key, EVE).encode());
+		let vc_type = VCType::PrivateDidVC;
+		let hash = BlakeTwo256::hash_of(&(&vc_type, &vc_property, &EVE, &vec![BOB]));
+		let signature = sr25519::Pair::from_seed(&BOB_SEED).sign(hash.as_ref());
+		let vc_id = [1; 32];
+
+		// a DID VC stored with sr25519 signatures and an sr25519 key in its property
+		let old_vc = (
+			hash,
+			EVE,
+			vec![BOB],
+			vec![signature],
+			Some(1u32),
+			0u32,
+			true,
+			vc_type,
+			vc_property,
+		);
+		frame_support::storage::unhashed::put_raw(
+			&VCs::<Test>::hashed_key_for(vc_id),
+			&old_vc.encode(),
+		);
+
+		migrations::v2::migrate::<Test>();
+
+		// the property stays as signed by the issuers
+		assert_eq!(VCs::<Test>::get(vc_id).unwrap().vc_property, vc_property);
+		assert_eq!(LegacyDidVCs::<Test>::get(vc_id), Some(()));
+		assert_eq!(
+			<VC as VCResolve<H256>>::decode_did_vc::<PrivateDidVC>(&vc_id),
+			Ok(PrivateDidVC { public_key: public_key.into(), did: EVE })
+		);
+	})
+}
+
+#[test]
+fn test_legacy_generic_vc_migration() {
+	new_test_ext().execute_with(|| {
+		let pair: sr25519::Pair = sr25519::Pair::from_seed(&BOB_SEED);
+		let (vc_id, vc) = signed_generic_vc(BOB, "QmdQYGSHZDGk6jDwCP2Sy4ybFAdH5PUvbt1oCcZzS1GucE");
+		let challenge = b"login to portal";
+		let owner_signature: MultiSignature = pair.sign(challenge).into();
+
+		// a GenericVC issued when its hash did not have to cover the CID
+		let hash = BlakeTwo256::hash_of(&b"off-chain document");
+		let legacy_vc =
+			VCStruct { hash, signatures: vec![pair.sign(hash.as_ref()).into()], is_vc_active: true, ..vc };
+		VCs::<Test>::insert(vc_id, legacy_vc.clone());
+		RLookup::<Test>::insert(vc_id, BOB);
+		assert!(!VC::verify_presentation(vc_id, challenge, &owner_signature).unwrap().is_vc_active);
+
+		migrations::v5::migrate::<Test>();
+
+		assert_eq!(LegacyGenericVCs::<Test>::get(vc_id), Some(()));
+		assert_eq!(VC::is_vc_active(&vc_id, &legacy_vc, None), Ok(true));
+		assert!(VC::verify_presentation(vc_id, challenge, &owner_signature).unwrap().is_vc_active);
+
+		// the VCs stored from now on must bind their CID
+		assert_eq!(
+			VC::is_vc_active(&[0; 32], &legacy_vc, None),
+			Err(Error::<Test>::VCPropertiesNotVerified.into())
+		);
+	})
+}
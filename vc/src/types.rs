@@ -15,4 +15,47 @@ pub struct GenericVC {
 pub struct InitialVCs {
     pub vc_id: VCid,
     pub vc_hex: VCHex,
-}
\ No newline at end of file
+}
+/// Pending transfer of a VC to a new owner
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct VCTransfer {
+    /// Did the VC is transferred to
+    pub new_owner: Did,
+    /// Issuers who have consented to the transfer
+    pub approvals: Vec<Did>,
+}
+
+/// Status of a VC, as returned by the runtime api
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VCStatus {
+    /// Did currently owning the VC
+    pub owner: Did,
+    /// If all the issuers have signed and the VC is not revoked
+    pub is_vc_active: bool,
+    /// If the VC has no uses left
+    pub is_vc_used: bool,
+    /// Number of times the VC has been used
+    pub used_count: u32,
+    /// Maximum number of times the VC can be used, `None` for unlimited uses
+    pub max_uses: Option<u32>,
+}
+
+/// Outcome of verifying that the holder presenting a VC is its owner
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PresentationVerdict {
+    /// Did currently owning the VC
+    pub owner: Did,
+    /// If the VC is active and still signed by the current keys of all its issuers
+    pub is_vc_active: bool,
+    /// If the VC has no uses left
+    pub is_vc_used: bool,
+    /// If the VC was deactivated after all the issuers had signed it
+    pub is_revoked: bool,
+    /// If the owner's current key is the key its Did had when the VC was stored or transferred
+    /// to it, false once the key was rotated and for VCs stored before the keys were recorded
+    pub is_owner_key_match: bool,
+    /// If the presentation payload of the challenge is signed by the current key of the owner
+    pub is_signature_valid: bool,
+}
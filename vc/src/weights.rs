@@ -0,0 +1,234 @@
+//! Weights for pallet_vc
+//!
+//! These are estimates of the signature verification and storage costs, counted from the
+//! storage accesses of each benchmark. They are not benchmark output yet, regenerate them from
+//! the benchmarks in `benchmarking.rs` on the reference hardware with
+//! `benchmark pallet --pallet pallet_vc --extrinsic '*'`.
+//!
+//! The issuer signatures are checked against every issuer key, so the verification work grows
+//! with the product of the issuer and signature counts. A GenericVC is checked against the
+//! council and the other signed types against the VCs they refer to, through the longest chain
+//! of versions, and both reserve the storage deposit. A TokenVC pays no deposit.
+//!
+//! Superseding a VC and activating a VC include deactivating the replaced VC, which searches
+//! `MAX_RETIRE_SCAN` blocks of the prune queue for room. Pruning a VC removes it from the index
+//! of every issuer and returns its deposit. Approving a transfer compares the approvals with
+//! every issuer, the last approval moves the VC to the new owner.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_vc.
+pub trait WeightInfo {
+	fn store(i: u32, s: u32) -> Weight;
+	fn store_generic_vc(i: u32, s: u32) -> Weight;
+	fn store_linked_vc(i: u32, s: u32) -> Weight;
+	fn supersede(i: u32, s: u32) -> Weight;
+	fn add_signature(i: u32) -> Weight;
+	fn add_signatures(n: u32, i: u32) -> Weight;
+	fn update_status() -> Weight;
+	fn request_transfer() -> Weight;
+	fn approve_transfer(i: u32) -> Weight;
+	fn force_approve_transfer() -> Weight;
+	fn cancel_transfer() -> Weight;
+	fn prune_vc(i: u32) -> Weight;
+}
+
+/// Weights for pallet_vc using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn store(i: u32, s: u32) -> Weight {
+		(41_280_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn store_generic_vc(i: u32, s: u32) -> Weight {
+		(58_410_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn store_linked_vc(i: u32, s: u32) -> Weight {
+		(54_730_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn supersede(i: u32, s: u32) -> Weight {
+		(87_960_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(39 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn add_signature(i: u32) -> Weight {
+		(38_914_000 as Weight)
+			.saturating_add((55_630_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((48_730_000 as Weight).saturating_mul(i as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn add_signatures(n: u32, i: u32) -> Weight {
+		(14_270_000 as Weight)
+			.saturating_add((41_380_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((55_630_000 as Weight).saturating_mul(n as Weight).saturating_mul(i as Weight))
+			.saturating_add((48_730_000 as Weight).saturating_mul(n as Weight).saturating_mul(i as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn update_status() -> Weight {
+		(58_920_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(32 as Weight))
+			.saturating_add(T::DbWeight::get().writes(13 as Weight))
+	}
+	fn request_transfer() -> Weight {
+		(24_310_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn approve_transfer(i: u32) -> Weight {
+		(29_870_000 as Weight)
+			.saturating_add((412_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn force_approve_transfer() -> Weight {
+		(21_650_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_transfer() -> Weight {
+		(19_720_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn prune_vc(i: u32) -> Weight {
+		(36_820_000 as Weight)
+			.saturating_add((3_140_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn store(i: u32, s: u32) -> Weight {
+		(41_280_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn store_generic_vc(i: u32, s: u32) -> Weight {
+		(58_410_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn store_linked_vc(i: u32, s: u32) -> Weight {
+		(54_730_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn supersede(i: u32, s: u32) -> Weight {
+		(87_960_000 as Weight)
+			.saturating_add((6_912_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((1_307_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((97_460_000 as Weight).saturating_mul(i as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(39 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(17 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+	fn add_signature(i: u32) -> Weight {
+		(38_914_000 as Weight)
+			.saturating_add((55_630_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add((48_730_000 as Weight).saturating_mul(i as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn add_signatures(n: u32, i: u32) -> Weight {
+		(14_270_000 as Weight)
+			.saturating_add((41_380_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((55_630_000 as Weight).saturating_mul(n as Weight).saturating_mul(i as Weight))
+			.saturating_add((48_730_000 as Weight).saturating_mul(n as Weight).saturating_mul(i as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(n as Weight)))
+	}
+	fn update_status() -> Weight {
+		(58_920_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(32 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(13 as Weight))
+	}
+	fn request_transfer() -> Weight {
+		(24_310_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn approve_transfer(i: u32) -> Weight {
+		(29_870_000 as Weight)
+			.saturating_add((412_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn force_approve_transfer() -> Weight {
+		(21_650_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn cancel_transfer() -> Weight {
+		(19_720_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn prune_vc(i: u32) -> Weight {
+		(36_820_000 as Weight)
+			.saturating_add((3_140_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(i as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(i as Weight)))
+	}
+}